@@ -0,0 +1,63 @@
+//! Time encoding with each `EncodeStrategy` over GF(2^31-1), to pick the size
+//! up to which `Encoder::new` caches a generator matrix. For each code the
+//! table shows the number of generator matrix elements n * (n + 2k), the time
+//! to build each encoder, and the time per message.
+//!
+//! Run with `cargo bench --bench encode`. One run on one core gave:
+//!
+//! ```text
+//!     n    k      elems  build G  interpolate/msg  G/msg
+//!    16    4        384  29.91µs          27.92µs 869.00ns
+//!    32    8       1536 100.76µs         166.13µs 3.62µs
+//!    64   16       6144 446.70µs         766.02µs 14.57µs
+//!   128   32      24576   5.93ms           5.65ms 14.50µs
+//!   256   64      98304  10.72ms          31.85ms 119.49µs
+//!   512  128     393216  55.12ms         226.65ms 528.61µs
+//!  1024  256    1572864 254.88ms            1.74s 3.23ms
+//! ```
+//!
+//! The generator matrix costs about as much to build as one message costs to
+//! interpolate and is much faster per message at every size, so speed alone
+//! never rules it out. `Encoder::MAX_GENERATOR_LEN` instead keeps it to codes
+//! of length up to 256, the size of the (128, 32) row, where it takes a few
+//! milliseconds to build and at most 256 KiB to keep.
+
+use std::time::{Duration, Instant};
+
+use berlewelch::{
+	encoder::{EncodeStrategy, Encoder},
+	field::Gfe2_31,
+	Encoding, Gfe,
+};
+use rand::Rng;
+
+fn main() {
+	let mut rng = rand::thread_rng();
+	println!("    n    k      elems  build G  interpolate/msg  G/msg");
+	for (n, k) in [(16, 4), (32, 8), (64, 16), (128, 32), (256, 64), (512, 128), (1024, 256)] {
+		let v = vec![Gfe::one(); n + 2 * k];
+		let messages = (0..(1 << 18) / n).map(|_| (0..n).map(|_| Gfe2_31::new(rng.gen_range(0..0x7fffffff))).collect::<Vec<_>>());
+		let messages = messages.collect::<Vec<_>>();
+		let reps = ((1 << 22) / (n * n)).clamp(1, messages.len());
+
+		let (interpolation, _) = time(|| Encoder::with_strategy(n, k, &v, Encoding::Systematic, EncodeStrategy::Interpolation));
+		let (generator, build_time) = time(|| Encoder::with_strategy(n, k, &v, Encoding::Systematic, EncodeStrategy::GeneratorMatrix));
+		let (expected, interpolate_time) = time(|| messages[..reps].iter().map(|r| interpolation.encode(r)).collect::<Vec<_>>());
+		let (encoded, generator_time) = time(|| messages[..reps].iter().map(|r| generator.encode(r)).collect::<Vec<_>>());
+		assert_eq!(encoded, expected);
+
+		println!(
+			"{n:5} {k:4} {:10} {:>8.2?} {:>16.2?} {:>6.2?}",
+			n * (n + 2 * k),
+			build_time,
+			interpolate_time / reps as u32,
+			generator_time / reps as u32
+		);
+	}
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+	let start = Instant::now();
+	let result = f();
+	(result, start.elapsed())
+}
@@ -0,0 +1,91 @@
+//! Time the half-GCD of random polynomials over GF(2^31-1) against running the
+//! Euclidean algorithm to the same point, to find the degree above which
+//! `Polynomial::extended_gcd` switches to the half-GCD.
+//!
+//! Run with `cargo bench --bench gcd`. With the half-GCD recursing all the way
+//! down (`HALF_GCD_CROSSOVER` set to 0) one run gave:
+//!
+//! ```text
+//! degree       euclid     half-gcd
+//!     16      19.92µs      26.85µs
+//!     32      60.30µs      78.42µs
+//!     64     179.76µs     285.73µs
+//!    128     753.00µs     749.01µs
+//!    256       2.40ms       2.27ms
+//!    512       8.37ms       7.46ms
+//!   1024      30.03ms      26.60ms
+//!   2048     114.07ms      74.44ms
+//! ```
+//!
+//! and with the crossover at 256:
+//!
+//! ```text
+//! degree       euclid     half-gcd
+//!     16      27.35µs      29.65µs
+//!     32      83.28µs      84.66µs
+//!     64     250.03µs     243.77µs
+//!    128     783.59µs     761.84µs
+//!    256       2.63ms       2.41ms
+//!    512       9.47ms       8.50ms
+//!   1024      34.72ms      25.51ms
+//!   2048     111.05ms      79.25ms
+//! ```
+//!
+//! Multiplication is schoolbook, so the half-GCD only wins by avoiding the
+//! Euclidean algorithm's per-step allocations, and the two are within noise of
+//! each other below a couple of hundred terms.
+
+use std::time::{Duration, Instant};
+
+use berlewelch::{field::Gfe2_31, polynomial::Polynomial};
+use rand::Rng;
+
+const DEGREES: [usize; 8] = [16, 32, 64, 128, 256, 512, 1024, 2048];
+
+fn main() {
+	println!("{:>6} {:>12} {:>12}", "degree", "euclid", "half-gcd");
+	for d in DEGREES {
+		let reps = (1 << 16) / d;
+		let pairs = (0..reps).map(|_| (rand_poly(d + 1), rand_poly(d))).collect::<Vec<_>>();
+		let euclid = time(|| {
+			for (a, b) in &pairs {
+				euclid_half(a, b);
+			}
+		}) / reps as u32;
+		let half = time(|| {
+			for (a, b) in &pairs {
+				Polynomial::half_gcd(a, b);
+			}
+		}) / reps as u32;
+		println!("{d:>6} {euclid:>12.2?} {half:>12.2?}");
+	}
+}
+
+fn time(f: impl FnOnce()) -> Duration {
+	let start = Instant::now();
+	f();
+	start.elapsed()
+}
+
+fn rand_poly(len: usize) -> Polynomial<0x7fffffff> {
+	let mut rng = rand::thread_rng();
+	let mut coeffs = (0..len).map(|_| Gfe2_31::new(rng.gen_range(0..0x7fffffff))).collect::<Vec<_>>();
+	coeffs[len - 1] = Gfe2_31::one();
+	Polynomial::new(coeffs)
+}
+
+/// The Euclidean algorithm with cofactors, stopped at the same remainders as
+/// the half-GCD: the first with degree below ceil(deg a / 2)
+fn euclid_half(a: &Polynomial<0x7fffffff>, b: &Polynomial<0x7fffffff>) -> [Polynomial<0x7fffffff>; 4] {
+	let m = a.degree().unwrap().div_ceil(2);
+	let (mut r0, mut r1) = (a.clone(), b.clone());
+	let (mut s0, mut s1) = (Polynomial::one(), Polynomial::zero());
+	let (mut t0, mut t1) = (Polynomial::zero(), Polynomial::one());
+	while r1.degree() >= Some(m) {
+		let (q, r) = r0.divide(&r1).unwrap();
+		let s = &s0 - &(&q * &s1);
+		let t = &t0 - &(&q * &t1);
+		(r0, r1, s0, s1, t0, t1) = (r1, r, s1, s, t1, t);
+	}
+	[s0, t0, s1, t1]
+}
@@ -59,7 +59,7 @@ fn ascii_to_c29(ascii: u8) -> Option<u8> {
 		b' ' => 26,
 		b'.' => 27,
 		b'_' => 28,
-		a if b'a' <= a && a <= b'z' => a - b'a',
+		a if a.is_ascii_lowercase() => a - b'a',
 		_ => return None,
 	})
 }
@@ -83,7 +83,7 @@ fn c29_to_gfe(msg: &str) -> Option<Vec<Gfe29>> {
 
 fn gfe_to_c29(msg: &[Gfe29]) -> Option<String> {
 	msg.iter()
-		.map(|&x| u8::try_from(*x).ok().and_then(|x| c29_to_ascii(x)))
+		.map(|&x| u8::try_from(*x).ok().and_then(c29_to_ascii))
 		.collect::<Option<Vec<u8>>>()
 		.map(|bytes| String::from_utf8(bytes).unwrap())
 }
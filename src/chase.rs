@@ -0,0 +1,115 @@
+use crate::{decode, field::Gfe};
+
+/// A codeword found by [`decode_chase`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChaseCandidate<const M: u32> {
+	/// The corrected encoded message, with the message in its first
+	/// len - 2k values
+	pub codeword: Vec<Gfe<M>>,
+	/// The sum over every position of the rank of the codeword's value in that
+	/// position's list of alternatives, counting a value missing from the list as
+	/// the length of the list
+	pub distance: usize,
+	/// The trial, counting from 1, that first produced this codeword
+	pub trial: usize,
+}
+
+/// The result of [`decode_chase`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChaseDecoding<const M: u32> {
+	/// Every distinct codeword found, sorted by increasing distance and then by
+	/// the trial that found it
+	pub candidates: Vec<ChaseCandidate<M>>,
+	/// The number of times [`decode`] was called
+	pub trials: usize,
+}
+
+/// Correct a message given a short list of alternative values for each position,
+/// ordered from most to least likely, where k is the error tolerance the message
+/// was encoded with.
+///
+/// This is Chase-II style decoding: each trial picks one alternative for every
+/// position and corrects the result with [`decode`]. Trials are made in order of
+/// the total rank of the alternatives chosen, starting with the most likely value
+/// everywhere, until every combination has been tried or `max_trials` is
+/// reached. Returns an error if no trial could be decoded.
+#[allow(clippy::result_unit_err)]
+pub fn decode_chase<const M: u32>(k: usize, alternatives: &[Vec<Gfe<M>>], max_trials: usize) -> Result<ChaseDecoding<M>, ()> {
+	if let Some(i) = alternatives.iter().position(|a| a.is_empty()) {
+		panic!("Position {i} has no alternative values")
+	}
+
+	let lens = alternatives.iter().map(|a| a.len()).collect::<Vec<_>>();
+	let max_sum = lens.iter().map(|len| len - 1).sum::<usize>();
+
+	let mut candidates: Vec<ChaseCandidate<M>> = Vec::new();
+	let mut trials = 0;
+	for sum in 0..=max_sum {
+		let mut patterns = Vec::new();
+		patterns_with_sum(&lens, sum, &mut Vec::new(), &mut patterns, max_trials - trials);
+		for pattern in patterns {
+			trials += 1;
+			let mut r = pattern.iter().zip(alternatives).map(|(&j, a)| a[j]).collect::<Vec<_>>();
+			if decode(k, &mut r).is_ok() && !candidates.iter().any(|c| c.codeword == r) {
+				candidates.push(ChaseCandidate {
+					distance: distance(&r, alternatives),
+					codeword: r,
+					trial: trials,
+				});
+			}
+		}
+		if trials == max_trials {
+			break;
+		}
+	}
+
+	if candidates.is_empty() {
+		return Err(());
+	}
+	candidates.sort_by_key(|c| (c.distance, c.trial));
+	Ok(ChaseDecoding { candidates, trials })
+}
+
+/// Push up to `limit` choices of an index below lens[i] for each position i whose
+/// indices add up to `sum`, extending `prefix`
+fn patterns_with_sum(lens: &[usize], sum: usize, prefix: &mut Vec<usize>, out: &mut Vec<Vec<usize>>, limit: usize) {
+	if out.len() == limit {
+		return;
+	}
+	let Some((&len, rest)) = lens.split_first() else {
+		if sum == 0 {
+			out.push(prefix.clone());
+		}
+		return;
+	};
+	// The remaining positions can make up at most this much of the sum
+	let rest_max = rest.iter().map(|len| len - 1).sum::<usize>();
+	for j in sum.saturating_sub(rest_max)..len.min(sum + 1) {
+		prefix.push(j);
+		patterns_with_sum(rest, sum - j, prefix, out, limit);
+		prefix.pop();
+	}
+}
+
+/// The total rank of the values of r in each position's list of alternatives
+fn distance<const M: u32>(r: &[Gfe<M>], alternatives: &[Vec<Gfe<M>>]) -> usize {
+	r.iter()
+		.zip(alternatives)
+		.map(|(x, a)| a.iter().position(|y| y == x).unwrap_or(a.len()))
+		.sum()
+}
+
+#[test]
+fn patterns_test() {
+	let mut patterns = Vec::new();
+	patterns_with_sum(&[2, 1, 3], 2, &mut Vec::new(), &mut patterns, 10);
+	assert_eq!(patterns, vec![vec![0, 0, 2], vec![1, 0, 1]]);
+
+	let mut patterns = Vec::new();
+	patterns_with_sum(&[2, 1, 3], 2, &mut Vec::new(), &mut patterns, 1);
+	assert_eq!(patterns, vec![vec![0, 0, 2]]);
+
+	let mut patterns = Vec::new();
+	patterns_with_sum(&[2, 2], 3, &mut Vec::new(), &mut patterns, 10);
+	assert!(patterns.is_empty());
+}
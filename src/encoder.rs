@@ -0,0 +1,91 @@
+use crate::{check_multipliers, encode_with, field::Gfe, generator_matrix_with, matrix::Matrix, Encoding};
+
+/// How an [`Encoder`] computes encoded messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodeStrategy {
+	/// Interpolate the message polynomial and evaluate it, as [`encode_with`]
+	/// does, which needs no extra memory
+	Interpolation,
+	/// Multiply the message by a generator matrix computed once when the encoder
+	/// is created, which takes O(n * (n + 2k)) operations per message
+	GeneratorMatrix,
+}
+
+/// Encodes messages of a fixed length n with the same error tolerance, column
+/// multipliers and encoding, producing exactly the same output as
+/// [`encode_with`]. Repeatedly encoding short messages is faster through a
+/// cached generator matrix, which [`Encoder::new`] uses for short codes.
+#[derive(Debug, Clone)]
+pub struct Encoder<const M: u32> {
+	n: usize,
+	k: usize,
+	v: Vec<Gfe<M>>,
+	encoding: Encoding,
+	generator: Option<Matrix<M>>,
+}
+
+impl<const M: u32> Encoder<M> {
+	/// The longest encoded message, n + 2k, for which [`Encoder::new`] caches the
+	/// generator matrix. In `benches/encode.rs` the matrix is faster per message
+	/// at every size, so the limit keeps the cache to small codes: up to this
+	/// length it holds at most 256 KiB and takes a few milliseconds to build.
+	pub const MAX_GENERATOR_LEN: usize = 256;
+
+	/// Create an encoder for messages of length n, using a generator matrix for
+	/// small codes
+	pub fn new(n: usize, k: usize, v: &[Gfe<M>], encoding: Encoding) -> Self {
+		let strategy = if n + 2 * k <= Self::MAX_GENERATOR_LEN {
+			EncodeStrategy::GeneratorMatrix
+		} else {
+			EncodeStrategy::Interpolation
+		};
+		Self::with_strategy(n, k, v, encoding, strategy)
+	}
+
+	/// Create an encoder for messages of length n that uses the given strategy
+	pub fn with_strategy(n: usize, k: usize, v: &[Gfe<M>], encoding: Encoding, strategy: EncodeStrategy) -> Self {
+		check_multipliers(n + 2 * k, v);
+		let generator = match strategy {
+			EncodeStrategy::Interpolation => None,
+			EncodeStrategy::GeneratorMatrix => Some(generator_matrix_with(n, k, v, encoding).transpose()),
+		};
+		Self {
+			n,
+			k,
+			v: v.to_vec(),
+			encoding,
+			generator,
+		}
+	}
+
+	pub fn strategy(&self) -> EncodeStrategy {
+		match self.generator {
+			Some(_) => EncodeStrategy::GeneratorMatrix,
+			None => EncodeStrategy::Interpolation,
+		}
+	}
+
+	/// Encode a message of length n
+	pub fn encode(&self, r: &[Gfe<M>]) -> Vec<Gfe<M>> {
+		if r.len() != self.n {
+			panic!("Expected a message of length {}, got {}", self.n, r.len())
+		}
+		match &self.generator {
+			// The transposed generator matrix times r is the row vector r G
+			Some(generator_t) => generator_t * r,
+			None => encode_with(self.k, r, &self.v, self.encoding),
+		}
+	}
+
+	/// Encode each of a batch of messages of length n. With the `rayon` feature
+	/// the messages are encoded in parallel.
+	pub fn encode_batch(&self, messages: &[Vec<Gfe<M>>]) -> Vec<Vec<Gfe<M>>> {
+		#[cfg(feature = "rayon")]
+		{
+			use rayon::prelude::*;
+			messages.par_iter().map(|r| self.encode(r)).collect()
+		}
+		#[cfg(not(feature = "rayon"))]
+		messages.iter().map(|r| self.encode(r)).collect()
+	}
+}
@@ -1,6 +1,7 @@
 use std::{
 	fmt::{Debug, Display},
 	ops::{Add, Deref, Mul},
+	str::FromStr,
 };
 
 pub type Gfe19 = Gfe<19>;
@@ -9,7 +10,7 @@ pub type Gfe2_31 = Gfe<0x7fffffff>; // GF(2^31-1)
 
 /// An element of the Galois field GF(M) where M is the constant declared in this module.
 /// NOTE: M must be prime
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Gfe<const M: u32>(u32);
 
 impl<const M: u32> Gfe<M> {
@@ -28,7 +29,25 @@ impl<const M: u32> Gfe<M> {
 	pub fn inverse(self) -> Self {
 		assert!(self.0 != 0);
 		let (_d, a, _b) = gcde(self.0 as i64, M as i64);
-		return Self::from(a);
+		Self::from(a)
+	}
+
+	/// How many products of two elements can be added to an element as u64 values
+	/// without overflowing, so that a running sum of products only needs reducing
+	/// mod M once every this many terms. This is 4 for M = 2^31 - 1 and grows
+	/// quickly for smaller fields.
+	pub const LAZY_PRODUCTS: u64 = (u64::MAX - (M as u64 - 1)) / ((M as u64 - 1) * (M as u64 - 1));
+
+	/// Compute self + a * b with a single reduction mod M
+	pub fn mul_add(self, a: Self, b: Self) -> Self {
+		Self(((self.0 as u64 + a.0 as u64 * b.0 as u64) % M as u64) as u32)
+	}
+
+	/// The sum of a_i * b_i over two slices of the same length. The products are
+	/// accumulated without reduction and reduced once at the end.
+	pub fn dot(a: &[Self], b: &[Self]) -> Self {
+		let sum = a.iter().zip(b).fold(0u128, |acc, (x, y)| acc + x.0 as u128 * y.0 as u128);
+		Self((sum % M as u128) as u32)
 	}
 
 	pub fn negation(self) -> Self {
@@ -103,6 +122,73 @@ impl<const M: u32> Debug for Gfe<M> {
 	}
 }
 
+/// Error returned when parsing a field element fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseGfeError;
+
+impl Display for ParseGfeError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "invalid field element")
+	}
+}
+
+impl std::error::Error for ParseGfeError {}
+
+/// Parse an integer in decimal, or hexadecimal with a 0x prefix, with an
+/// optional sign. The value is reduced mod M, so integers of any size and
+/// negative integers are accepted.
+impl<const M: u32> FromStr for Gfe<M> {
+	type Err = ParseGfeError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim();
+		let (negative, s) = match s.strip_prefix('-') {
+			Some(rest) => (true, rest),
+			None => (false, s.strip_prefix('+').unwrap_or(s)),
+		};
+		let (radix, digits) = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+			Some(rest) => (16, rest),
+			None => (10, s),
+		};
+		if digits.is_empty() {
+			return Err(ParseGfeError);
+		}
+
+		// Reduce as we go so that the value never overflows
+		let mut value = 0u64;
+		for c in digits.chars() {
+			let digit = c.to_digit(radix).ok_or(ParseGfeError)?;
+			value = (value * radix as u64 + digit as u64) % M as u64;
+		}
+		let x = Self(value as u32);
+		Ok(if negative { x.negation() } else { x })
+	}
+}
+
+/// A xorshift generator for the pseudo-random choices made by randomized
+/// algorithms such as equal-degree factorization and Wiedemann's algorithm. It
+/// is seeded with fixed values so that those algorithms are deterministic, and
+/// is not suitable for anything that needs unpredictable values.
+pub(crate) struct Xorshift(u64);
+
+impl Xorshift {
+	/// Start a sequence from a seed, mixed so that nearby seeds give unrelated
+	/// sequences
+	pub(crate) fn new(seed: u64) -> Self {
+		// The state must never be zero, which xorshift can't leave
+		let state = 0x9e3779b97f4a7c15 ^ seed.wrapping_add(1).wrapping_mul(0xbf58476d1ce4e5b9);
+		Self(state.max(1))
+	}
+
+	/// The next field element, close to uniformly distributed
+	pub(crate) fn next_gfe<const M: u32>(&mut self) -> Gfe<M> {
+		self.0 ^= self.0 << 13;
+		self.0 ^= self.0 >> 7;
+		self.0 ^= self.0 << 17;
+		Gfe((self.0 % M as u64) as u32)
+	}
+}
+
 pub fn gcde(x: i64, y: i64) -> (i64, i64, i64) {
 	if y == 0 {
 		return (x, 1, 0);
@@ -112,3 +198,39 @@ pub fn gcde(x: i64, y: i64) -> (i64, i64, i64) {
 	let (d, ap, bp) = gcde(y, r);
 	(d, bp, ap - bp * (x / y))
 }
+
+#[test]
+fn parse_test() {
+	assert_eq!("12".parse::<Gfe19>(), Ok(Gfe::new(12)));
+	assert_eq!(" 40 ".parse::<Gfe19>(), Ok(Gfe::new(2)));
+	assert_eq!("-1".parse::<Gfe19>(), Ok(Gfe::new(18)));
+	assert_eq!("+0x1f".parse::<Gfe19>(), Ok(Gfe::new(12)));
+	assert_eq!("-0XFF".parse::<Gfe19>(), Ok(Gfe::from(-255)));
+	assert_eq!("123456789012345678901234567890".parse::<Gfe2_31>(), Ok(Gfe::new(281742486)));
+	assert_eq!("".parse::<Gfe19>(), Err(ParseGfeError));
+	assert_eq!("0x".parse::<Gfe19>(), Err(ParseGfeError));
+	assert_eq!("1f".parse::<Gfe19>(), Err(ParseGfeError));
+	assert_eq!("--3".parse::<Gfe19>(), Err(ParseGfeError));
+
+	for x in [0, 1, 5, 0x7ffffffe] {
+		assert_eq!(Gfe2_31::new(x).to_string().parse(), Ok(Gfe2_31::new(x)));
+	}
+}
+
+#[test]
+fn lazy_reduction_test() {
+	let big = Gfe2_31::new(0x7ffffffe);
+	assert_eq!(big.mul_add(big, big), big + big * big);
+	let a = vec![big; 1000];
+	let b = (0..1000).map(|i| Gfe2_31::new(0x7fffffff - 1 - i)).collect::<Vec<_>>();
+	let expected = a.iter().zip(&b).fold(Gfe::zero(), |acc, (&x, &y)| acc + x * y);
+	assert_eq!(Gfe::dot(&a, &b), expected);
+	assert_eq!(Gfe19::dot(&[], &[]), Gfe::zero());
+
+	// LAZY_PRODUCTS maximal products fit in a u64 on top of a maximal element, but
+	// one more doesn't
+	let (max, lazy) = (0x7ffffffe_u64, Gfe2_31::LAZY_PRODUCTS);
+	assert_eq!(lazy, 4);
+	assert!((lazy * max * max).checked_add(max).is_some());
+	assert!(((lazy + 1) * max).checked_mul(max).is_none());
+}
@@ -1,117 +1,467 @@
-use self::{matrix::Matrix, polynomial::Polynomial};
+use self::{
+	matrix::{Matrix, Solution},
+	polynomial::Polynomial,
+};
 
+pub mod chase;
+pub mod encoder;
 pub mod field;
+pub mod list_decoding;
 pub mod matrix;
+pub mod multivariate;
 pub mod polynomial;
+pub mod soft_decoding;
+pub mod sparse_matrix;
+pub mod sparse_polynomial;
 #[cfg(test)]
 mod test;
 
-pub use self::field::Gfe;
+pub use self::{
+	chase::decode_chase,
+	encoder::{EncodeStrategy, Encoder},
+	field::Gfe,
+	list_decoding::{list_decode, list_decode_with},
+	soft_decoding::{decode_soft, decode_soft_with},
+};
 
 /// Construct an error resistant message for a given message r. k is the number
 /// of general errors to protect against. The encoded message will have 2k extra
 /// values.
 pub fn encode<const M: u32>(k: usize, r: &[Gfe<M>]) -> Vec<Gfe<M>> {
+	encode_grs(k, r, &vec![Gfe::one(); r.len() + 2 * k])
+}
+
+/// Construct an error resistant message using a generalized Reed-Solomon code
+/// with column multipliers v. Symbol i of the encoded message is v_i * f(i)
+/// where f is the polynomial of degree at most n-1 such that v_i * f(i) = r_i
+/// for every i < n, so the message is still present in the first n values.
+/// v must contain r.len() + 2k nonzero values.
+pub fn encode_grs<const M: u32>(k: usize, r: &[Gfe<M>], v: &[Gfe<M>]) -> Vec<Gfe<M>> {
+	encode_with(k, r, v, Encoding::Systematic)
+}
+
+/// How a message is turned into the polynomial that is evaluated to produce the
+/// encoded message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+	/// The message is the values of the polynomial (divided by the column
+	/// multipliers) at 0..n-1, so it appears unchanged at the start of the
+	/// encoded message.
+	Systematic,
+	/// The message is the coefficients of the polynomial, lowest degree first.
+	Coefficients,
+}
+
+/// Construct an error resistant message for a given message r using the column
+/// multipliers v and the given encoding. k is the number of general errors to
+/// protect against. The encoded message will have r.len() + 2k values.
+pub fn encode_with<const M: u32>(k: usize, r: &[Gfe<M>], v: &[Gfe<M>], encoding: Encoding) -> Vec<Gfe<M>> {
 	// Length of initial message
 	let n = r.len();
 
 	if n > M as usize {
 		panic!("Message too long for this field")
 	}
+	check_multipliers(n + 2 * k, v);
 
-	// Convert message to a set of points
-	let points = r.iter().enumerate().map(|x| (Gfe::from(x.0 as i64), *x.1)).collect::<Vec<_>>();
-	// Construct the unique polynomial of degree at most n-1 that passes through
-	// these points.
-	let poly = Polynomial::from_points(&points);
-	// Construct the error-resistant message by including an additional 2k
-	// points on the polynomial in the message.
-	(0..(n + 2 * k)).map(|x| poly.eval(Gfe::from(x as i64))).collect()
+	let poly = match encoding {
+		Encoding::Systematic => {
+			// Convert message to a set of points, dividing out the column multipliers
+			let points = r.iter().enumerate().map(|x| (Gfe::from(x.0 as i64), *x.1 * v[x.0].inverse())).collect::<Vec<_>>();
+			// Construct the unique polynomial of degree at most n-1 that passes through
+			// these points.
+			Polynomial::from_points(&points)
+		}
+		Encoding::Coefficients => Polynomial::new(r.to_vec()),
+	};
+	// Construct the error-resistant message by evaluating the polynomial at
+	// n + 2k points.
+	let values = poly.eval_range(Gfe::zero(), n + 2 * k);
+	values.into_iter().zip(v).map(|(y, &c)| c * y).collect()
 }
 
 /// Correct a message with up to k corruptions. It will be present in the first
 /// r.len()-2*k items in r.
+#[allow(clippy::result_unit_err)]
 pub fn decode<const M: u32>(k: usize, r: &mut [Gfe<M>]) -> Result<(), ()> {
+	decode_grs(k, r, &vec![Gfe::one(); r.len()])
+}
+
+/// Correct a message encoded with [`encode_grs`] using the column multipliers v,
+/// with up to k corruptions. It will be present in the first r.len()-2*k items
+/// in r.
+#[allow(clippy::result_unit_err)]
+pub fn decode_grs<const M: u32>(k: usize, r: &mut [Gfe<M>], v: &[Gfe<M>]) -> Result<(), ()> {
+	decode_with(k, r, v, Encoding::Systematic).map(|_| ())
+}
+
+/// Correct a message encoded with [`encode_with`] using the column multipliers v
+/// and the given encoding, with up to k corruptions. The corrected encoded
+/// message is written back to r and the original message is returned in the
+/// representation matching the encoding.
+#[allow(clippy::result_unit_err)]
+pub fn decode_with<const M: u32>(k: usize, r: &mut [Gfe<M>], v: &[Gfe<M>], encoding: Encoding) -> Result<Vec<Gfe<M>>, ()> {
+	decode_erasures_with(k, r, v, encoding, &[])
+}
+
+/// Correct a message whose values at the positions in `erasures` are known to be
+/// lost, with up to e further corruptions at unknown positions, where
+/// 2e + erasures.len() <= 2k. It will be present in the first r.len()-2*k items
+/// in r.
+#[allow(clippy::result_unit_err)]
+pub fn decode_erasures<const M: u32>(k: usize, r: &mut [Gfe<M>], erasures: &[usize]) -> Result<(), ()> {
+	decode_erasures_with(k, r, &vec![Gfe::one(); r.len()], Encoding::Systematic, erasures).map(|_| ())
+}
+
+/// Correct a message encoded with [`encode_with`] using the column multipliers v
+/// and the given encoding, treating the values at the positions in `erasures` as
+/// lost. Up to e further corruptions can be corrected where
+/// 2e + erasures.len() <= 2k. The corrected encoded message is written back to
+/// r, including the erased values, and the original message is returned in the
+/// representation matching the encoding.
+#[allow(clippy::result_unit_err)]
+pub fn decode_erasures_with<const M: u32>(
+	k: usize,
+	r: &mut [Gfe<M>],
+	v: &[Gfe<M>],
+	encoding: Encoding,
+	erasures: &[usize],
+) -> Result<Vec<Gfe<M>>, ()> {
+	let p = decode_polynomial(k, r, v, erasures)?;
+
+	let values = p.eval_range(Gfe::zero(), r.len());
+	for ((x, y), &c) in r.iter_mut().zip(values).zip(v) {
+		*x = c * y;
+	}
+
+	let n = r.len() - 2 * k;
+	Ok(message_of(&p, &r[..n], encoding))
+}
+
+/// Encode each of a batch of messages with [`encode`]. When the messages all
+/// have the same length they share an [`Encoder`], which may use a generator
+/// matrix built for this batch. Callers encoding many batches of one shape
+/// should hold an [`Encoder`] and use [`Encoder::encode_batch`] instead. With
+/// the `rayon` feature the messages are encoded in parallel.
+pub fn encode_batch<const M: u32>(k: usize, messages: &[Vec<Gfe<M>>]) -> Vec<Vec<Gfe<M>>> {
+	let n = messages.first().map_or(0, |r| r.len());
+	let encoder = messages
+		.iter()
+		.all(|r| r.len() == n)
+		.then(|| Encoder::new(n, k, &vec![Gfe::one(); n + 2 * k], Encoding::Systematic));
+	let encode_one = |r: &Vec<Gfe<M>>| match &encoder {
+		Some(encoder) => encoder.encode(r),
+		None => encode(k, r),
+	};
+
+	#[cfg(feature = "rayon")]
+	{
+		use rayon::prelude::*;
+		messages.par_iter().map(encode_one).collect()
+	}
+	#[cfg(not(feature = "rayon"))]
+	messages.iter().map(encode_one).collect()
+}
+
+/// Correct each of a batch of messages in place with [`decode`], returning the
+/// result for each one. With the `rayon` feature the messages are decoded in
+/// parallel.
+pub fn decode_batch<const M: u32>(k: usize, messages: &mut [Vec<Gfe<M>>]) -> Vec<Result<(), ()>> {
+	#[cfg(feature = "rayon")]
+	{
+		use rayon::prelude::*;
+		messages.par_iter_mut().map(|r| decode(k, r)).collect()
+	}
+	#[cfg(not(feature = "rayon"))]
+	messages.iter_mut().map(|r| decode(k, r)).collect()
+}
+
+/// The message encoded by the polynomial p, given the first n values of its
+/// encoding where n is the message length
+pub(crate) fn message_of<const M: u32>(p: &Polynomial<M>, prefix: &[Gfe<M>], encoding: Encoding) -> Vec<Gfe<M>> {
+	match encoding {
+		Encoding::Systematic => prefix.to_vec(),
+		Encoding::Coefficients => (0..prefix.len()).map(|i| p.coeff(i)).collect(),
+	}
+}
+
+/// Find the polynomial f of degree at most r.len()-2k-1 such that v_i * f(i) = r_i
+/// for all but at most k values of i using the Berlekamp-Welch algorithm. The
+/// positions in `erasures` are ignored, and each one reduces the number of
+/// other corruptions that can be corrected by a half.
+fn decode_polynomial<const M: u32>(k: usize, r: &[Gfe<M>], v: &[Gfe<M>], erasures: &[usize]) -> Result<Polynomial<M>, ()> {
 	// Ensure the message is long enough given the number of errors (at least one character besides error-correction ones)
 	if r.len() < 1 + 2 * k {
 		return Err(())
 	}
+	check_multipliers(r.len(), v);
+	if let Some(&i) = erasures.iter().find(|&&i| i >= r.len()) {
+		panic!("Erasure position {i} out of range for a message of length {}", r.len())
+	}
 
 	// Length of the message
 	let n = r.len() - 2 * k;
-	// Total number of message packets and the total number of unknown coefficients
-	// Number of rows and columns in non-augmented matrix
+	// The positions that were not erased, which are the only ones used to decode
+	let known = (0..r.len()).filter(|i| !erasures.contains(i)).collect::<Vec<_>>();
+	if known.len() < n {
+		return Err(());
+	}
+	// Number of errors that can still be corrected among the known positions
+	let k = (known.len() - n) / 2;
+	// Number of unknown coefficients, which is the number of columns in the non-augmented matrix
 	let z = n + 2 * k;
-	assert_eq!(z, r.len());
-
-	let mut mat = Matrix {
-		// Each row corresponds to a point received in the message
-		m: z,
-		// Columns are stored as follows:
-		// The first n+k columns are coefficients a_0..a_{n+k-1} of polynomial Q(x) (degree n+k-1)
-		// The next k columns are coefficients b_0..b_{k-1} of polynomial E(x) (degree k, leading coefficient = 1 by definition)
-		// There is one more column to represent the augmented nature of the matrix
-		n: z + 1,
-		// Entries initialized to zero to start
-		elems: vec![Gfe::from(0); z * (z + 1)],
-	};
-	// Initialize entries of the matrix. For each row i (i = index in message and input to polynomial as well)
-	for i in 0..z {
+
+	// Each row corresponds to a point received in the message, and the entries are initialized to zero to start.
+	// Columns are stored as follows:
+	// The first n+k columns are coefficients a_0..a_{n+k-1} of polynomial Q(x) (degree n+k-1)
+	// The next k columns are coefficients b_0..b_{k-1} of polynomial E(x) (degree k, leading coefficient = 1 by definition)
+	// There is one more column to represent the augmented nature of the matrix
+	let mut mat = Matrix::zeros(known.len(), z + 1);
+	// Initialize entries of the matrix. Row `row` corresponds to the known position i, which is also the input to the polynomial
+	for (row, &i) in known.iter().enumerate() {
+		// The received value with its column multiplier divided out, so that y_i = f(i) for uncorrupted values
+		let y = r[i] * v[i].inverse();
+		// Running powers i^0..i^(n+k) shared by both polynomials
+		let powers = successive_powers(Gfe::from(i as i64), n + k + 1);
 		// For each coefficient a of Q(x)
-		for a in 0..(n + k) {
+		for (a, &power) in powers[..(n + k)].iter().enumerate() {
 			// Set coefficient a_{ai} = i^{ai}
-			*mat.elem_mut(i, a) = Gfe::new(i as u32).power(a as i32); // a_{0..n+k-1} * i^(0..n+k-1)
+			mat[(row, a)] = power; // a_{0..n+k-1} * i^(0..n+k-1)
 		}
-		for b in 0..k {
+		for (b, &power) in powers[..k].iter().enumerate() {
 			// Set the coefficient b_{bi} = -
-			*mat.elem_mut(i, n + k + b) = (r[i] * Gfe::new(i as u32).power(b as i32)).negation();
-			// b_{0..k-1} * -r_i * i^(0..k-1)
+			mat[(row, n + k + b)] = (y * power).negation();
+			// b_{0..k-1} * -y_i * i^(0..k-1)
 		}
-		*mat.elem_mut(i, n + 2 * k) = r[i] * Gfe::new(i as u32).power(k as i32); // = r_i * i^k
-	}
-	mat.row_reduce();
-
-	// TODO: assert that:
-	// 1. the matrix implies that there is a unique solution OR
-	// the matrix implies that there are infinitely many solutions and all of the parameters are in the error polynomial (is this second part even a thing?)
-	// 2. the system is not inconsistent
-
-	// This assumes that there is a unique solution or the only parameters are the errors. It makes use of this since it can
-	// then simply ignore error parameters by setting them to zero. Some more work might be required to detect cases where the
-	// system is inconsistent or otherwise invalid.
-	let mut q_coeffs = Vec::new();
-	for i in 0..(n + k) {
-		if let Some(row) = (0..z).find(|&row| mat.elem(row, i) == Gfe::one()) {
-			q_coeffs.push(mat.elem(row, z));
-		} else {
-			// ERROR: No determinate value for coefficient i of Q polynomial
-			return Err(());
+		mat[(row, n + 2 * k)] = y * powers[k]; // = y_i * i^k
+	}
+	// When there are fewer than k errors the system is underdetermined, but every
+	// solution (Q, E) has Q = f * E for the same f, so any one of them will do.
+	// The system is inconsistent when there are too many errors.
+	let (solution, unique) = match mat.solve_augmented().0 {
+		Solution::Unique(x) => (x, true),
+		Solution::Parametric { particular: x, .. } => (x, false),
+		Solution::Inconsistent => return Err(()),
+	};
+	let q = Polynomial::new(solution[..(n + k)].to_vec());
+	let mut e_coeffs = solution[(n + k)..].to_vec();
+	e_coeffs.push(Gfe::one());
+	let e = Polynomial::new(e_coeffs);
+
+	let (p, rem) = q.divide(&e).expect("E(x) is monic");
+	if !rem.is_zero() {
+		// Nonzero remainder indicates decoding failed
+		return Err(());
+	}
+
+	// Check the result rather than trusting the linear algebra: f must disagree
+	// with at most k of the known values, and the error locator must vanish at
+	// exactly those positions
+	let errors = known
+		.iter()
+		.copied()
+		.filter(|&i| v[i] * p.eval(Gfe::from(i as i64)) != r[i])
+		.collect::<Vec<_>>();
+	if errors.len() > k || !locator_matches(&e, &known, &errors, unique) {
+		return Err(());
+	}
+
+	Ok(p)
+}
+
+/// Whether the error locator E(x) found by decoding accounts for the errors
+/// found by comparing the decoded polynomial with the known positions. Every
+/// error must be a root of E. When the system had a unique solution there were
+/// exactly deg E errors, so E must also have deg E distinct roots among the
+/// known positions and no others. Otherwise E is the true locator times an
+/// arbitrary factor whose roots may or may not be in the domain.
+fn locator_matches<const M: u32>(e: &Polynomial<M>, known: &[usize], errors: &[usize], unique: bool) -> bool {
+	let roots = e.roots_in(known.iter().map(|&i| Gfe::from(i as i64)));
+	let error_roots = errors.iter().filter(|&&i| roots.contains(&Gfe::from(i as i64))).count();
+	if error_roots != errors.len() {
+		return false;
+	}
+	!unique || (Some(roots.len()) == e.degree() && roots.len() == errors.len())
+}
+
+/// Compute the 2k syndromes of a message encoded with [`encode`], which are all
+/// zero exactly when r is a valid encoded message. Returns an error if r is
+/// shorter than 1 + 2k.
+#[allow(clippy::result_unit_err)]
+pub fn syndromes<const M: u32>(k: usize, r: &[Gfe<M>]) -> Result<Vec<Gfe<M>>, ()> {
+	syndromes_grs(k, r, &vec![Gfe::one(); r.len()])
+}
+
+/// Compute the 2k syndromes of a message encoded with [`encode_grs`] or
+/// [`encode_with`] using the column multipliers v. Syndrome j is
+/// sum_i u_i * i^j * r_i where u are the [`dual_multipliers`] of v, which is zero
+/// for every j < 2k exactly when r is a codeword. This takes O(z * k) operations
+/// rather than the O(z^3) of decoding. Returns an error if r is shorter than
+/// 1 + 2k or v is not the same length as r.
+#[allow(clippy::result_unit_err)]
+pub fn syndromes_grs<const M: u32>(k: usize, r: &[Gfe<M>], v: &[Gfe<M>]) -> Result<Vec<Gfe<M>>, ()> {
+	let z = r.len();
+	if z < 1 + 2 * k || v.len() != z {
+		return Err(());
+	}
+	check_multipliers(z, v);
+
+	let denominators = lagrange_denominators(z);
+	let mut syndromes = vec![Gfe::zero(); 2 * k];
+	for (i, ((&y, &c), &d)) in r.iter().zip(v).zip(&denominators).enumerate() {
+		let mut term = y * (c * d).inverse();
+		for s in syndromes.iter_mut() {
+			*s = *s + term;
+			term = term * Gfe::from(i as i64);
 		}
 	}
+	Ok(syndromes)
+}
+
+/// The products prod_{j != i} (i - j) over j < len for each i < len, which are
+/// the denominators of the Lagrange basis polynomials for the points 0..len-1
+fn lagrange_denominators<const M: u32>(len: usize) -> Vec<Gfe<M>> {
+	// prod_{j != i} (i - j) = (-1)^(len-1-i) * i! * (len-1-i)!
+	let mut factorials = vec![Gfe::one(); len];
+	for i in 1..len {
+		factorials[i] = factorials[i - 1] * Gfe::from(i as i64);
+	}
+	(0..len)
+		.map(|i| {
+			let d = factorials[i] * factorials[len - 1 - i];
+			if (len - 1 - i) % 2 == 0 {
+				d
+			} else {
+				d.negation()
+			}
+		})
+		.collect()
+}
 
-	let mut e_coeffs = Vec::new();
-	for i in (n + k)..(n + 2 * k) {
-		if let Some(row) = (0..z).find(|&row| (0..i).all(|j| mat.elem(row, j) == Gfe::zero()) && mat.elem(row, i) == Gfe::one()) {
-			e_coeffs.push(mat.elem(row, z));
-		} else {
-			// If this error is a parameter just we are assuming it is zero.
-			e_coeffs.push(Gfe::zero());
+/// The generator matrix of the code implemented by [`encode`] for messages of
+/// length n, so that the encoding of a message is the row vector r G.
+pub fn generator_matrix<const M: u32>(n: usize, k: usize) -> Matrix<M> {
+	generator_matrix_with(n, k, &vec![Gfe::one(); n + 2 * k], Encoding::Systematic)
+}
+
+/// The n by n + 2k generator matrix of the code implemented by [`encode_with`]
+/// with the column multipliers v and the given encoding, so that the encoding of
+/// a message is the row vector r G. Row l is the encoding of the message with a
+/// one in position l, so with systematic encoding G starts with the identity.
+pub fn generator_matrix_with<const M: u32>(n: usize, k: usize, v: &[Gfe<M>], encoding: Encoding) -> Matrix<M> {
+	let z = n + 2 * k;
+	if n > M as usize {
+		panic!("Message too long for this field")
+	}
+	check_multipliers(z, v);
+
+	match encoding {
+		// Symbol i of the encoding of coefficient l is v_i * i^l
+		Encoding::Coefficients => Matrix::vandermonde(&(0..z).map(|i| Gfe::from(i as i64)).collect::<Vec<_>>(), n).transpose(),
+		// Symbol i of the encoding of unit vector l is v_i / v_l * L_l(i) where L_l is
+		// the Lagrange basis polynomial for the points 0..n-1, and
+		// L_l(i) = W(i) / ((i - l) * prod_{j != l} (l - j)) with W(x) = prod_j (x - j)
+		Encoding::Systematic => {
+			let denominators = lagrange_denominators::<M>(n);
+			let w = (n..z)
+				.map(|i| (0..n).fold(Gfe::one(), |acc, j| acc * Gfe::from(i as i64 - j as i64)))
+				.collect::<Vec<_>>();
+			Matrix::from_fn(n, z, |l, i| {
+				if i >= n {
+					w[i - n] * (Gfe::from(i as i64 - l as i64) * denominators[l] * v[l]).inverse()
+				} else if i == l {
+					v[l].inverse()
+				} else {
+					Gfe::zero()
+				}
+			})
 		}
 	}
-	e_coeffs.push(Gfe::new(1));
+	.scale_columns(v)
+}
 
-	let q = Polynomial::new(q_coeffs);
-	let e = Polynomial::new(e_coeffs);
-	let (p, rem) = q.divide(&e);
+/// The 2k by n + 2k parity-check matrix of the code implemented by
+/// [`encode_grs`] or [`encode_with`] with the column multipliers v and any
+/// encoding, so that H r is the [`syndromes_grs`] of r and G H^T = 0.
+pub fn parity_check_matrix_grs<const M: u32>(n: usize, k: usize, v: &[Gfe<M>]) -> Matrix<M> {
+	let z = n + 2 * k;
+	check_multipliers(z, v);
+	// Row j is the dual code's multipliers times i^j
+	let u = dual_multipliers(v);
+	Matrix::vandermonde(&(0..z).map(|i| Gfe::from(i as i64)).collect::<Vec<_>>(), 2 * k)
+		.transpose()
+		.scale_columns(&u)
+}
 
-	if rem != Polynomial::zero() {
-		// Nonzero remainder indicates decoding failed
-		return Err(());
+/// The parity-check matrix of the code implemented by [`encode`] for messages of
+/// length n, so that H r is the [`syndromes`] of r.
+pub fn parity_check_matrix<const M: u32>(n: usize, k: usize) -> Matrix<M> {
+	parity_check_matrix_grs(n, k, &vec![Gfe::one(); n + 2 * k])
+}
+
+/// Whether r is a valid message encoded with [`encode`] with error tolerance k.
+/// A message too short to hold 2k check values is never valid.
+pub fn is_codeword<const M: u32>(k: usize, r: &[Gfe<M>]) -> bool {
+	is_codeword_grs(k, r, &vec![Gfe::one(); r.len()])
+}
+
+/// Whether r is a valid message encoded with the column multipliers v, with any
+/// encoding. False if r is too short or v does not match its length.
+pub fn is_codeword_grs<const M: u32>(k: usize, r: &[Gfe<M>], v: &[Gfe<M>]) -> bool {
+	syndromes_grs(k, r, v).is_ok_and(|syndromes| syndromes.iter().all(|&s| s == Gfe::zero()))
+}
+
+/// Check a message for corruption without correcting it. Unlike [`decode`], which
+/// corrects up to k corruptions, this detects any number of corruptions up to
+/// 2k and fails whenever r is not a valid encoded message.
+#[allow(clippy::result_unit_err)]
+pub fn detect<const M: u32>(k: usize, r: &[Gfe<M>]) -> Result<(), ()> {
+	detect_grs(k, r, &vec![Gfe::one(); r.len()])
+}
+
+/// Check a message encoded with the column multipliers v for corruption without
+/// correcting it, as [`detect`] does
+#[allow(clippy::result_unit_err)]
+pub fn detect_grs<const M: u32>(k: usize, r: &[Gfe<M>], v: &[Gfe<M>]) -> Result<(), ()> {
+	if is_codeword_grs(k, r, v) {
+		Ok(())
+	} else {
+		Err(())
 	}
+}
 
-	for i in 0..(n + 2 * k) {
-		r[i] = p.eval(Gfe::from(i as i64));
+/// Compute the column multipliers of the dual of the generalized Reed-Solomon
+/// code of length z with column multipliers v. The dual of the code with
+/// message length n is the code with message length z-n and the returned
+/// multipliers, which are given by v'_i = 1 / (v_i * prod_{j != i} (i - j)).
+pub fn dual_multipliers<const M: u32>(v: &[Gfe<M>]) -> Vec<Gfe<M>> {
+	let z = v.len();
+	check_multipliers(z, v);
+	(0..z)
+		.map(|i| {
+			let x = Gfe::from(i as i64);
+			let d = (0..z).filter(|&j| j != i).fold(Gfe::one(), |acc, j| acc * (x + Gfe::from(j as i64).negation()));
+			(v[i] * d).inverse()
+		})
+		.collect()
+}
+
+/// The powers x^0..x^(len-1)
+fn successive_powers<const M: u32>(x: Gfe<M>, len: usize) -> Vec<Gfe<M>> {
+	let mut powers = Vec::with_capacity(len);
+	let mut power = Gfe::one();
+	for _ in 0..len {
+		powers.push(power);
+		power = power * x;
 	}
+	powers
+}
 
-	Ok(())
+pub(crate) fn check_multipliers<const M: u32>(z: usize, v: &[Gfe<M>]) {
+	if v.len() != z {
+		panic!("Expected {z} column multipliers, got {}", v.len())
+	}
+	if v.contains(&Gfe::zero()) {
+		panic!("Column multipliers must be nonzero")
+	}
 }
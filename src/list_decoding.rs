@@ -0,0 +1,274 @@
+use std::fmt::Display;
+
+use crate::{
+	check_multipliers,
+	field::Gfe,
+	matrix::{Matrix, Solution},
+	message_of,
+	multivariate::MultivariatePolynomial,
+	polynomial::Polynomial,
+	Encoding,
+};
+
+/// Error returned by the list decoder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListDecodeError {
+	/// The received message is shorter than 1 + 2k, so it has no room for a
+	/// message, as for [`crate::decode`]
+	TooShort,
+	/// The radius is beyond what can be decoded with a multiplicity of at most
+	/// [`MAX_MULTIPLICITY`]. Holds the largest radius that can be, as given by
+	/// [`max_radius`].
+	RadiusTooLarge { max_radius: usize },
+}
+
+impl Display for ListDecodeError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			ListDecodeError::TooShort => write!(f, "message too short for its error tolerance"),
+			ListDecodeError::RadiusTooLarge { max_radius } => {
+				write!(f, "list decoding radius too large, at most {max_radius} errors can be decoded")
+			}
+		}
+	}
+}
+
+impl std::error::Error for ListDecodeError {}
+
+/// The largest interpolation multiplicity the list decoder will use. The
+/// interpolation is a dense linear system in about z * s^2 / 2 unknowns for a
+/// code of length z, so the multiplicity needed right at the Guruswami-Sudan
+/// bound (28 for a length 16 code with 4 message values) would mean solving a
+/// system of thousands of unknowns. Every radius up to the unique decoding
+/// radius k needs much less than this.
+pub const MAX_MULTIPLICITY: usize = 8;
+
+/// Find every message whose encoding with [`crate::encode`] differs from r in at
+/// most `radius` positions, where k is the error tolerance the message was
+/// encoded with. Unlike [`crate::decode`], the radius may exceed k, in which
+/// case more than one message may be returned. Candidates are sorted by
+/// increasing distance from r. Returns an error if r is shorter than 1 + 2k or
+/// the radius is beyond [`max_radius`].
+pub fn list_decode<const M: u32>(k: usize, r: &[Gfe<M>], radius: usize) -> Result<Vec<Vec<Gfe<M>>>, ListDecodeError> {
+	list_decode_with(k, r, &vec![Gfe::one(); r.len()], Encoding::Systematic, radius)
+}
+
+/// List decode a message encoded with [`crate::encode_with`] using the column
+/// multipliers v and the given encoding, returning every message within
+/// `radius` of r in the representation matching the encoding.
+///
+/// This is the Guruswami-Sudan algorithm: a bivariate polynomial Q(x, y) of low
+/// (1, n-1)-weighted degree is interpolated through every received point with
+/// multiplicity s, so that y - f(x) divides Q for every message polynomial f
+/// that agrees with enough of the points. Those factors are then found with
+/// Roth-Ruckenstein root finding. The multiplicity needed grows quickly with
+/// the radius and is capped at [`MAX_MULTIPLICITY`], so radii beyond
+/// [`max_radius`] are an error.
+pub fn list_decode_with<const M: u32>(
+	k: usize,
+	r: &[Gfe<M>],
+	v: &[Gfe<M>],
+	encoding: Encoding,
+	radius: usize,
+) -> Result<Vec<Vec<Gfe<M>>>, ListDecodeError> {
+	let z = r.len();
+	check_multipliers(z, v);
+	if z < 1 + 2 * k {
+		return Err(ListDecodeError::TooShort);
+	}
+	// Length of the message and the maximum degree of its polynomial
+	let n = z - 2 * k;
+	let dk = n - 1;
+
+	let max_radius = max_radius(z, dk);
+	if radius > max_radius {
+		return Err(ListDecodeError::RadiusTooLarge { max_radius });
+	}
+
+	// Received values with the column multipliers divided out
+	let points = r
+		.iter()
+		.zip(v)
+		.enumerate()
+		.map(|(i, (&y, &c))| (Gfe::from(i as i64), y * c.inverse()))
+		.collect::<Vec<_>>();
+
+	let candidates = if dk == 0 {
+		// Constant polynomials can be checked directly
+		let mut values = points.iter().map(|p| p.1).collect::<Vec<_>>();
+		values.sort();
+		values.dedup();
+		values.into_iter().map(Polynomial::constant).collect()
+	} else {
+		let (s, d) = interpolation_parameters(z, dk, z - radius).expect("radius is at most max_radius");
+		let q = interpolate(&points, s, d, dk);
+		let mut candidates = Vec::new();
+		roth_ruckenstein(&q, dk, &mut Vec::new(), &mut candidates);
+		candidates
+	};
+
+	// Keep only the candidates that are close enough to what was received
+	let mut found = Vec::new();
+	for f in candidates {
+		let values = f.eval_range(Gfe::zero(), z);
+		let codeword = values.iter().zip(v).map(|(&y, &c)| c * y).collect::<Vec<_>>();
+		let distance = codeword.iter().zip(r).filter(|(a, b)| a != b).count();
+		if distance <= radius && !found.iter().any(|(_, g)| g == &f) {
+			found.push((distance, f));
+		}
+	}
+	found.sort_by_key(|(distance, _)| *distance);
+
+	Ok(found
+		.into_iter()
+		.map(|(_, f)| {
+			let codeword = f.eval_range(Gfe::zero(), n).iter().zip(v).map(|(&y, &c)| c * y).collect::<Vec<_>>();
+			message_of(&f, &codeword, encoding)
+		})
+		.collect())
+}
+
+/// The largest number of errors t that the list decoder can correct in a code
+/// of length z whose message polynomials have degree at most dk. This is the
+/// Guruswami-Sudan bound, the largest t with (z - t)^2 > z * dk, lowered to what
+/// can be reached with a multiplicity of at most [`MAX_MULTIPLICITY`].
+pub fn max_radius(z: usize, dk: usize) -> usize {
+	if dk == 0 {
+		return z - 1;
+	}
+	let mut agreement = 1;
+	while agreement * agreement <= z * dk {
+		agreement += 1;
+	}
+	while agreement < z && interpolation_parameters(z, dk, agreement).is_none() {
+		agreement += 1;
+	}
+	z.saturating_sub(agreement)
+}
+
+/// Find the smallest multiplicity s, and the weighted degree bound d less than
+/// agreement times s, such that there are more monomials x^a y^b with
+/// a + dk * b <= d than interpolation constraints. Returns None if that needs a
+/// multiplicity above [`MAX_MULTIPLICITY`].
+fn interpolation_parameters(z: usize, dk: usize, agreement: usize) -> Option<(usize, usize)> {
+	(1..=MAX_MULTIPLICITY).find_map(|s| {
+		let d = agreement * s - 1;
+		let monomials = (0..=d / dk).map(|b| d - dk * b + 1).sum::<usize>();
+		(monomials > z * s * (s + 1) / 2).then_some((s, d))
+	})
+}
+
+/// Interpolate a nonzero Q(x, y) with (1, dk)-weighted degree at most d that has
+/// a zero of multiplicity s at each point
+fn interpolate<const M: u32>(points: &[(Gfe<M>, Gfe<M>)], s: usize, d: usize, dk: usize) -> MultivariatePolynomial<M> {
+	// Unknown coefficients q_{a,b} of x^a y^b
+	let monomials = (0..=d / dk)
+		.flat_map(|b| (0..=(d - dk * b)).map(move |a| (a, b)))
+		.collect::<Vec<_>>();
+	let binomial = binomials::<M>(d + 1);
+
+	// Every Hasse derivative Q^[u,v] with u + v < s vanishes at each point:
+	// sum_{a,b} C(a,u) C(b,v) x^(a-u) y^(b-v) q_{a,b} = 0
+	let constraints = points.len() * s * (s + 1) / 2;
+	let mut mat = Matrix::zeros(constraints, monomials.len());
+	let mut row = 0;
+	for &(x, y) in points {
+		let x_powers = (0..=d).map(|e| x.power(e as i32)).collect::<Vec<_>>();
+		let y_powers = (0..=d / dk).map(|e| y.power(e as i32)).collect::<Vec<_>>();
+		for u in 0..s {
+			for v in 0..(s - u) {
+				for (col, &(a, b)) in monomials.iter().enumerate() {
+					if a >= u && b >= v {
+						mat[(row, col)] = binomial[a][u] * binomial[b][v] * x_powers[a - u] * y_powers[b - v];
+					}
+				}
+				row += 1;
+			}
+		}
+	}
+	// There are more unknowns than constraints so the homogeneous system has a
+	// nonzero solution
+	let q = match mat.solve(&vec![Gfe::zero(); constraints]).0 {
+		Solution::Parametric { nullspace, .. } => nullspace.into_iter().next().unwrap(),
+		_ => unreachable!("an underdetermined homogeneous system has nonzero solutions"),
+	};
+	let terms = monomials.iter().zip(q).map(|(&(a, b), c)| (vec![a, b], c)).collect();
+	MultivariatePolynomial::new(2, terms)
+}
+
+/// Pascal's triangle of binomial coefficients mod M up to C(len-1, len-1)
+fn binomials<const M: u32>(len: usize) -> Vec<Vec<Gfe<M>>> {
+	let mut rows = Vec::<Vec<Gfe<M>>>::with_capacity(len);
+	for i in 0..len {
+		let row = (0..=i)
+			.map(|j| {
+				if j == 0 || j == i {
+					Gfe::one()
+				} else {
+					rows[i - 1][j - 1] + rows[i - 1][j]
+				}
+			})
+			.collect();
+		rows.push(row);
+	}
+	rows
+}
+
+/// Find the polynomials f of degree at most dk such that y - f(x) divides Q(x, y),
+/// one coefficient at a time from the constant term up. The coefficients
+/// chosen so far are in `prefix`.
+fn roth_ruckenstein<const M: u32>(q: &MultivariatePolynomial<M>, dk: usize, prefix: &mut Vec<Gfe<M>>, found: &mut Vec<Polynomial<M>>) {
+	// Divide out the largest power of x so that Q(0, y) is nonzero
+	let shift = q.terms().map(|(e, _)| e[0]).min().unwrap_or(0);
+	let q = MultivariatePolynomial::new(2, q.terms().map(|(e, c)| (vec![e[0] - shift, e[1]], c)).collect());
+
+	// The next coefficient of f must be a root of Q(0, y)
+	let q0 = q.coefficient_of(0, 0).to_univariate(1).unwrap();
+	for gamma in q0.roots() {
+		prefix.push(gamma);
+		if prefix.len() == dk + 1 {
+			found.push(Polynomial::new(prefix.clone()));
+		} else {
+			// Q(x, x y + gamma) has a root f'(x) exactly when Q has the root gamma + x f'(x)
+			roth_ruckenstein(&substitute(&q, gamma), dk, prefix, found);
+		}
+		prefix.pop();
+	}
+}
+
+/// Compute Q(x, x y + gamma)
+fn substitute<const M: u32>(q: &MultivariatePolynomial<M>, gamma: Gfe<M>) -> MultivariatePolynomial<M> {
+	let max_b = q.degree_in(1).unwrap_or(0);
+	let binomial = binomials::<M>(max_b + 1);
+	let mut terms = Vec::new();
+	for (e, c) in q.terms() {
+		let (a, b) = (e[0], e[1]);
+		// x^a (x y + gamma)^b = sum_j C(b, j) gamma^(b-j) x^(a+j) y^j
+		for (j, &choose) in binomial[b].iter().enumerate() {
+			terms.push((vec![a + j, j], c * choose * gamma.power((b - j) as i32)));
+		}
+	}
+	MultivariatePolynomial::new(2, terms)
+}
+
+#[test]
+fn roth_ruckenstein_test() {
+	// Q(x, y) = (y - f)(y - g) x^2 for f = 1 + 2x and g = 3 + x^2
+	let f = Polynomial::<19>::new(vec![Gfe::new(1), Gfe::new(2), Gfe::new(0)]);
+	let g = Polynomial::<19>::new(vec![Gfe::new(3), Gfe::new(0), Gfe::new(1)]);
+	let factor = |p: &Polynomial<19>| {
+		let mut terms = vec![(vec![0, 1], Gfe::one())];
+		terms.extend(p.coeffs().iter().enumerate().map(|(a, &c)| (vec![a, 0], c.negation())));
+		MultivariatePolynomial::new(2, terms)
+	};
+	let x2 = MultivariatePolynomial::new(2, vec![(vec![2, 0], Gfe::one())]);
+	let q = &(&factor(&f) * &factor(&g)) * &x2;
+
+	let mut found = Vec::new();
+	roth_ruckenstein(&q, 2, &mut Vec::new(), &mut found);
+	assert_eq!(found.len(), 2);
+	assert!(found.contains(&f) && found.contains(&g));
+
+	assert_eq!(max_radius(16, 3), 8);
+	assert_eq!(max_radius(5, 0), 4);
+}
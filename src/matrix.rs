@@ -1,39 +1,146 @@
-use std::fmt::Display;
+use std::{
+	fmt::Display,
+	ops::{Add, Index, IndexMut, Mul, Range},
+};
 
 use crate::field::Gfe;
+#[cfg(test)]
+use crate::test::gfe_vec;
 
 /// A matrix over GF(p) with m rows and n columns
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Matrix<const M: u32> {
-	pub m: usize,
-	pub n: usize,
-	pub elems: Vec<Gfe<M>>,
+	m: usize,
+	n: usize,
+	/// The elements in row-major order
+	elems: Vec<Gfe<M>>,
 }
 
 impl<const M: u32> Matrix<M> {
+	/// The m by n matrix of zeros
+	pub fn zeros(m: usize, n: usize) -> Self {
+		Self {
+			m,
+			n,
+			elems: vec![Gfe::zero(); m * n],
+		}
+	}
+
+	/// The n by n identity matrix
+	pub fn identity(n: usize) -> Self {
+		Self::from_fn(n, n, |i, j| if i == j { Gfe::one() } else { Gfe::zero() })
+	}
+
+	/// Construct a matrix from its rows, which must all have the same length
+	pub fn from_rows<R: AsRef<[Gfe<M>]>>(rows: &[R]) -> Self {
+		let n = rows.first().map_or(0, |row| row.as_ref().len());
+		if let Some(row) = rows.iter().find(|row| row.as_ref().len() != n) {
+			panic!("Expected rows of length {n}, got {}", row.as_ref().len())
+		}
+		Self {
+			m: rows.len(),
+			n,
+			elems: rows.iter().flat_map(|row| row.as_ref().iter().copied()).collect(),
+		}
+	}
+
+	/// The m by n matrix whose element at row i and column j is f(i, j)
+	pub fn from_fn(m: usize, n: usize, mut f: impl FnMut(usize, usize) -> Gfe<M>) -> Self {
+		let mut elems = Vec::with_capacity(m * n);
+		for i in 0..m {
+			for j in 0..n {
+				elems.push(f(i, j));
+			}
+		}
+		Self { m, n, elems }
+	}
+
+	/// The Vandermonde matrix with n columns whose row i is the powers
+	/// x_i^0..x_i^(n-1)
+	pub fn vandermonde(xs: &[Gfe<M>], n: usize) -> Self {
+		let mut elems = Vec::with_capacity(xs.len() * n);
+		for &x in xs {
+			let mut power = Gfe::one();
+			for _ in 0..n {
+				elems.push(power);
+				power = power * x;
+			}
+		}
+		Self { m: xs.len(), n, elems }
+	}
+
+	/// The number of rows
+	pub fn m(&self) -> usize {
+		self.m
+	}
+
+	/// The number of columns
+	pub fn n(&self) -> usize {
+		self.n
+	}
+
+	/// The elements in row-major order
+	pub fn elems(&self) -> &[Gfe<M>] {
+		&self.elems
+	}
+
+	pub fn row(&self, i: usize) -> &[Gfe<M>] {
+		&self.elems[i * self.n..(i + 1) * self.n]
+	}
+
+	pub fn row_mut(&mut self, i: usize) -> &mut [Gfe<M>] {
+		&mut self.elems[i * self.n..(i + 1) * self.n]
+	}
+
+	pub fn rows(&self) -> impl Iterator<Item = &[Gfe<M>]> {
+		(0..self.m).map(|i| self.row(i))
+	}
+
+	pub fn col(&self, j: usize) -> impl Iterator<Item = Gfe<M>> + '_ {
+		(0..self.m).map(move |i| self.elem(i, j))
+	}
+
+	pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = Gfe<M>> + '_> {
+		(0..self.n).map(|j| self.col(j))
+	}
+
+	/// The matrix made of the given ranges of rows and columns of this one
+	pub fn submatrix(&self, rows: Range<usize>, cols: Range<usize>) -> Self {
+		Self::from_fn(rows.len(), cols.len(), |i, j| self.elem(rows.start + i, cols.start + j))
+	}
+
 	/// Multiply row i by scalar c
 	pub fn mul_row(&mut self, i: usize, c: Gfe<M>) {
-		let (_m, n) = (self.m, self.n);
-		for j in 0..n {
-			self.elems[i * n + j] = self.elems[i * n + j] * c;
+		for x in self.row_mut(i) {
+			*x = *x * c;
 		}
 	}
 
 	/// Add c * row[i1] to row[i2]
 	pub fn add_c_row_to(&mut self, i1: usize, i2: usize, c: Gfe<M>) {
-		let (_m, n) = (self.m, self.n);
-		for j in 0..n {
-			self.elems[i2 * n + j] = self.elems[i2 * n + j] + self.elems[i1 * n + j] * c;
-		}
+		let (src, dst) = self.two_rows_mut(i1, i2);
+		add_c_slice_to(src, dst, c);
 	}
 
 	/// Swap rows i1 and i2
 	pub fn swap_rows(&mut self, i1: usize, i2: usize) {
-		let (_m, n) = (self.m, self.n);
-		for j in 0..n {
-			let tmp = self.elems[i1 * n + j];
-			self.elems[i1 * n + j] = self.elems[i2 * n + j];
-			self.elems[i2 * n + j] = tmp;
+		if i1 != i2 {
+			let (a, b) = self.two_rows_mut(i1, i2);
+			a.swap_with_slice(b);
+		}
+	}
+
+	/// Borrow two different rows at once
+	fn two_rows_mut(&mut self, i1: usize, i2: usize) -> (&mut [Gfe<M>], &mut [Gfe<M>]) {
+		assert_ne!(i1, i2, "Cannot borrow row {i1} twice");
+		let n = self.n;
+		let (lo, hi) = (i1.min(i2), i1.max(i2));
+		let (first, second) = self.elems.split_at_mut(hi * n);
+		let (a, b) = (&mut first[lo * n..(lo + 1) * n], &mut second[..n]);
+		if i1 < i2 {
+			(a, b)
+		} else {
+			(b, a)
 		}
 	}
 
@@ -45,67 +152,937 @@ impl<const M: u32> Matrix<M> {
 		&mut self.elems[i * self.n + j]
 	}
 
-	/// Attempt to row reduce the matrix as far as possible down the diagonal from left to right
+	/// Attempt to row reduce the matrix as far as possible down the diagonal from
+	/// left to right. With the `rayon` feature, matrices of at least
+	/// [`PARALLEL_ROW_REDUCE_ELEMS`] elements are eliminated in parallel.
 	pub fn row_reduce(&mut self) {
+		self.row_reduce_with(cfg!(feature = "rayon") && self.m * self.n >= PARALLEL_ROW_REDUCE_ELEMS);
+	}
+
+	/// Row reduce, updating the rows in parallel during elimination if `parallel`
+	/// is set, which needs the `rayon` feature.
+	///
+	/// Elimination adds one product to every element per pivot. The elements are
+	/// held as u64 sums that are only reduced mod M every [`Gfe::LAZY_PRODUCTS`]
+	/// pivots, or when one is needed as a pivot or a multiplier, rather than after
+	/// every product.
+	fn row_reduce_with(&mut self, parallel: bool) {
 		let (m, n) = (self.m, self.n);
+		let modulus = M as u64;
+		let mut work = self.elems.iter().map(|&x| *x as u64).collect::<Vec<_>>();
+		// Pivots eliminated since every element was last reduced
+		let mut unreduced = 0;
 
 		// The next row is the index of the earliest row that doesn't have a pivot yet
 		let mut next_row = 0;
 		// Reduce the matrix by each column from left-to-right
 		for column in 0..n {
 			// Find the next row with a non zero element at this column that is not already a pivot row.
-			if let Some(nonzero_row) = (next_row..m).find(|&row| self.elem(row, column) != Gfe::zero()) {
+			if let Some(nonzero_row) = (next_row..m).find(|&row| work[row * n + column] % modulus != 0) {
 				// Swap that row with the whatever row is current in the position of the next to-be pivot row.
-				self.swap_rows(nonzero_row, next_row);
+				if nonzero_row != next_row {
+					let (above, below) = work.split_at_mut(nonzero_row * n);
+					above[next_row * n..(next_row + 1) * n].swap_with_slice(&mut below[..n]);
+				}
 				// Rename the pivot row to just row
 				let row = next_row;
-				// Set the pivot element of this row to 1
-				self.mul_row(row, self.elem(row, column).inverse());
+				// Set the pivot element of this row to 1, leaving the row reduced
+				let pivot = &mut work[row * n..(row + 1) * n];
+				let inverse = *Gfe::<M>::from((pivot[column] % modulus) as i64).inverse() as u64;
+				for x in pivot {
+					*x = *x % modulus * inverse % modulus;
+				}
 				// Add multiples of this row to all other rows such that elements in the same column as
 				// this row's pivot are zeroed out
-				for other_row in (0..row).chain((row + 1)..m) {
-					self.add_c_row_to(row, other_row, self.elem(other_row, column).negation());
+				eliminate::<M>(&mut work, n, row, column, parallel);
+				unreduced += 1;
+				if unreduced == Gfe::<M>::LAZY_PRODUCTS {
+					work.iter_mut().for_each(|x| *x %= modulus);
+					unreduced = 0;
 				}
 				// The following row will be the location of the next pivot
 				next_row += 1;
 			}
 		}
+
+		for (x, w) in self.elems.iter_mut().zip(work) {
+			*x = Gfe::from((w % modulus) as i64);
+		}
+	}
+
+	/// The (row, column) position of the first nonzero element of each row that
+	/// has one, which are the pivots when the matrix is row reduced
+	fn pivots(&self) -> Vec<(usize, usize)> {
+		(0..self.m)
+			.filter_map(|row| (0..self.n).find(|&col| self.elem(row, col) != Gfe::zero()).map(|col| (row, col)))
+			.collect()
+	}
+
+	/// The reduced row echelon form of this matrix
+	pub fn rref(&self) -> Self {
+		let mut mat = self.clone();
+		mat.row_reduce();
+		mat
+	}
+
+	/// The number of linearly independent rows, which is also the number of
+	/// linearly independent columns
+	pub fn rank(&self) -> usize {
+		self.rref().pivots().len()
+	}
+
+	/// A basis of the solutions of A x = 0, which is empty if the only solution is
+	/// zero
+	pub fn nullspace(&self) -> Vec<Vec<Gfe<M>>> {
+		match self.solve(&vec![Gfe::zero(); self.m]).0 {
+			Solution::Parametric { nullspace, .. } => nullspace,
+			_ => Vec::new(),
+		}
+	}
+
+	/// Multiply column j by c_j for every j
+	pub fn scale_columns(mut self, c: &[Gfe<M>]) -> Self {
+		if c.len() != self.n {
+			panic!("Expected {} column scales, got {}", self.n, c.len())
+		}
+		for i in 0..self.m {
+			for (x, &c) in self.row_mut(i).iter_mut().zip(c) {
+				*x = *x * c;
+			}
+		}
+		self
+	}
+
+	pub fn transpose(&self) -> Self {
+		Self::from_fn(self.n, self.m, |i, j| self.elem(j, i))
+	}
+
+	/// The determinant of a square matrix, computed by reducing it to upper
+	/// triangular form while keeping track of the row swaps
+	pub fn determinant(&self) -> Gfe<M> {
+		self.check_square();
+		let mut mat = self.clone();
+		let mut det = Gfe::one();
+		for column in 0..mat.n {
+			let Some(row) = (column..mat.m).find(|&row| mat.elem(row, column) != Gfe::zero()) else {
+				return Gfe::zero();
+			};
+			if row != column {
+				mat.swap_rows(row, column);
+				det = det.negation();
+			}
+			let pivot = mat.elem(column, column);
+			det = det * pivot;
+			let inverse = pivot.inverse();
+			for other_row in (column + 1)..mat.m {
+				mat.add_c_row_to(column, other_row, (mat.elem(other_row, column) * inverse).negation());
+			}
+		}
+		det
+	}
+
+	/// The inverse of a square matrix, or None if it is singular
+	pub fn inverse(&self) -> Option<Self> {
+		self.check_square();
+		let n = self.n;
+		// Row reduce [A | I] into [I | A^-1]
+		let identity = Self::identity(n);
+		let mut mat = Self::from_fn(n, 2 * n, |i, j| if j < n { self.elem(i, j) } else { identity.elem(i, j - n) });
+		mat.row_reduce();
+		if (0..n).any(|i| mat.elem(i, i) != Gfe::one()) {
+			return None;
+		}
+		Some(mat.submatrix(0..n, n..(2 * n)))
+	}
+
+	/// Factor a square matrix as P A = L U, or return None if it is singular. The
+	/// factorization can be reused to solve A x = b for many right hand sides b
+	/// in O(n^2) operations each, rather than row reducing A every time.
+	pub fn plu(&self) -> Option<Plu<M>> {
+		self.check_square();
+		let n = self.n;
+		let mut lu = self.clone();
+		let mut perm = (0..n).collect::<Vec<_>>();
+		for column in 0..n {
+			let row = (column..n).find(|&row| lu.elem(row, column) != Gfe::zero())?;
+			lu.swap_rows(row, column);
+			perm.swap(row, column);
+			let inverse = lu.elem(column, column).inverse();
+			for other_row in (column + 1)..n {
+				// Store the multiplier in the zeroed position, where it becomes part of L
+				let c = lu.elem(other_row, column) * inverse;
+				for j in (column + 1)..n {
+					lu[(other_row, j)] = lu.elem(other_row, j) + (c * lu.elem(column, j)).negation();
+				}
+				lu[(other_row, column)] = c;
+			}
+		}
+		Some(Plu { lu, perm })
+	}
+
+	fn check_square(&self) {
+		if self.m != self.n {
+			panic!("Expected a square matrix, got {}x{}", self.m, self.n)
+		}
+	}
+
+	/// Solve the system A x = b where A is this matrix, without modifying it.
+	/// Returns the solutions along with the pivot columns of the reduced matrix.
+	pub fn solve(&self, b: &[Gfe<M>]) -> (Solution<M>, Vec<usize>) {
+		if b.len() != self.m {
+			panic!("Expected a right hand side of length {}, got {}", self.m, b.len())
+		}
+		Self::from_fn(self.m, self.n + 1, |i, j| if j < self.n { self.elem(i, j) } else { b[i] }).solve_augmented()
+	}
+
+	/// Solve the system whose augmented matrix is this matrix, so that the last
+	/// column is the right hand side, without modifying it. Returns the solutions
+	/// along with the pivot columns of the reduced matrix, not including the
+	/// augmented column.
+	pub fn solve_augmented(&self) -> (Solution<M>, Vec<usize>) {
+		if self.n == 0 {
+			panic!("An augmented matrix needs at least one column")
+		}
+		let mut mat = self.clone();
+		mat.row_reduce();
+		// Number of unknowns
+		let vars = self.n - 1;
+
+		// A pivot in the augmented column means 0 = 1
+		let mut pivots = mat.pivots();
+		let inconsistent = pivots.last().is_some_and(|p| p.1 == vars);
+		if inconsistent {
+			pivots.pop();
+		}
+		let pivot_columns = pivots.iter().map(|p| p.1).collect::<Vec<_>>();
+		if inconsistent {
+			return (Solution::Inconsistent, pivot_columns);
+		}
+
+		// Setting every free variable to zero gives a particular solution
+		let mut particular = vec![Gfe::zero(); vars];
+		for &(row, col) in &pivots {
+			particular[col] = mat.elem(row, vars);
+		}
+		// Setting one free variable to one and the rest to zero in the homogeneous
+		// system gives a basis of its solutions
+		let nullspace = (0..vars)
+			.filter(|col| !pivot_columns.contains(col))
+			.map(|free| {
+				let mut x = vec![Gfe::zero(); vars];
+				x[free] = Gfe::one();
+				for &(row, col) in &pivots {
+					x[col] = mat.elem(row, free).negation();
+				}
+				x
+			})
+			.collect::<Vec<_>>();
+
+		let solution = if nullspace.is_empty() {
+			Solution::Unique(particular)
+		} else {
+			Solution::Parametric { particular, nullspace }
+		};
+		(solution, pivot_columns)
+	}
+}
+
+impl<const M: u32> Index<(usize, usize)> for Matrix<M> {
+	type Output = Gfe<M>;
+
+	fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+		&self.elems[i * self.n + j]
+	}
+}
+
+impl<const M: u32> IndexMut<(usize, usize)> for Matrix<M> {
+	fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+		&mut self.elems[i * self.n + j]
+	}
+}
+
+impl<const M: u32> Add for &'_ Matrix<M> {
+	type Output = Matrix<M>;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		if (self.m, self.n) != (rhs.m, rhs.n) {
+			panic!("Cannot add a {}x{} matrix to a {}x{} matrix", rhs.m, rhs.n, self.m, self.n)
+		}
+		Matrix {
+			m: self.m,
+			n: self.n,
+			elems: self.elems.iter().zip(&rhs.elems).map(|(&a, &b)| a + b).collect(),
+		}
+	}
+}
+
+impl<const M: u32> Add for Matrix<M> {
+	type Output = Matrix<M>;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		&self + &rhs
+	}
+}
+
+impl<const M: u32> Mul for &'_ Matrix<M> {
+	type Output = Matrix<M>;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		if self.n != rhs.m {
+			panic!("Cannot multiply a {}x{} matrix by a {}x{} matrix", self.m, self.n, rhs.m, rhs.n)
+		}
+		let mut product = Matrix::zeros(self.m, rhs.n);
+		// Each row of the product is summed as u64 values, reduced every
+		// Gfe::LAZY_PRODUCTS terms and once at the end
+		let modulus = M as u64;
+		let mut sums = vec![0; rhs.n];
+		for i in 0..self.m {
+			sums.fill(0);
+			for (l, &a) in self.row(i).iter().enumerate() {
+				for (x, &y) in sums.iter_mut().zip(rhs.row(l)) {
+					*x += *a as u64 * *y as u64;
+				}
+				if (l as u64 + 1) % Gfe::<M>::LAZY_PRODUCTS == 0 {
+					sums.iter_mut().for_each(|x| *x %= modulus);
+				}
+			}
+			for (x, &sum) in product.row_mut(i).iter_mut().zip(&sums) {
+				*x = Gfe::from((sum % modulus) as i64);
+			}
+		}
+		product
+	}
+}
+
+impl<const M: u32> Mul for Matrix<M> {
+	type Output = Matrix<M>;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		&self * &rhs
+	}
+}
+
+/// Multiply a matrix by a column vector
+impl<const M: u32> Mul<&'_ [Gfe<M>]> for &'_ Matrix<M> {
+	type Output = Vec<Gfe<M>>;
+
+	fn mul(self, rhs: &[Gfe<M>]) -> Self::Output {
+		if self.n != rhs.len() {
+			panic!("Cannot multiply a {}x{} matrix by a vector of length {}", self.m, self.n, rhs.len())
+		}
+		self.rows().map(|row| Gfe::dot(row, rhs)).collect()
+	}
+}
+
+/// Solve V a = b in O(n^2) operations, where V is the square
+/// [`Matrix::vandermonde`] matrix of xs, so that a is the coefficients of the
+/// polynomial of degree less than n with value b_i at x_i. Returns None if the
+/// xs are not distinct.
+pub fn solve_vandermonde<const M: u32>(xs: &[Gfe<M>], b: &[Gfe<M>]) -> Option<Vec<Gfe<M>>> {
+	let n = check_lengths(xs.len(), b.len());
+	// Newton divided differences, where c_i = f[x_0, ..., x_i]
+	let mut c = b.to_vec();
+	for level in 1..n {
+		for i in (level..n).rev() {
+			let d = xs[i] + xs[i - level].negation();
+			if d == Gfe::zero() {
+				return None;
+			}
+			c[i] = (c[i] + c[i - 1].negation()) * d.inverse();
+		}
 	}
+	// Expand c_0 + (x - x_0)(c_1 + (x - x_1)(c_2 + ...)) from the inside out
+	let mut a = vec![Gfe::zero(); n];
+	for i in (0..n).rev() {
+		// a = a * (x - x_i) + c_i
+		for j in (1..n).rev() {
+			a[j] = a[j - 1] + a[j] * xs[i].negation();
+		}
+		a[0] = a[0] * xs[i].negation() + c[i];
+	}
+	Some(a)
+}
+
+/// Solve C a = b in O(n^2) operations, where C is the Cauchy matrix with
+/// elements 1 / (x_i - y_j). Returns None if C is not defined or is singular,
+/// which happens when the xs or the ys are not distinct or some x_i = y_j.
+///
+/// The solution is the residues of the rational function sum_j a_j / (x - y_j),
+/// which is determined by interpolating its numerator at the xs.
+pub fn solve_cauchy<const M: u32>(xs: &[Gfe<M>], ys: &[Gfe<M>], b: &[Gfe<M>]) -> Option<Vec<Gfe<M>>> {
+	let n = check_lengths(xs.len(), b.len());
+	check_lengths(n, ys.len());
+	// prod_{l != skip} (x - points_l)
+	let product = |points: &[Gfe<M>], x: Gfe<M>, skip: Option<usize>| {
+		(0..n)
+			.filter(|&l| Some(l) != skip)
+			.fold(Gfe::one(), |acc, l| acc * (x + points[l].negation()))
+	};
+
+	// With A(x) = prod (x - x_i) and B(x) = prod (x - y_j),
+	// a_j = A(y_j) / B'(y_j) * sum_i b_i B(x_i) / (A'(x_i) (y_j - x_i))
+	let mut weights = Vec::with_capacity(n);
+	for i in 0..n {
+		let d = product(xs, xs[i], Some(i));
+		if d == Gfe::zero() {
+			return None;
+		}
+		weights.push(b[i] * product(ys, xs[i], None) * d.inverse());
+	}
+	let mut a = Vec::with_capacity(n);
+	for j in 0..n {
+		let d = product(ys, ys[j], Some(j));
+		if d == Gfe::zero() {
+			return None;
+		}
+		let mut sum = Gfe::zero();
+		for (&x, &w) in xs.iter().zip(&weights) {
+			let diff = ys[j] + x.negation();
+			if diff == Gfe::zero() {
+				return None;
+			}
+			sum = sum + w * diff.inverse();
+		}
+		a.push(product(xs, ys[j], None) * d.inverse() * sum);
+	}
+	Some(a)
+}
+
+/// Solve T x = b in O(n^2) operations with Levinson recursion, where T is the
+/// Toeplitz matrix with the given first column and first row, so that
+/// T_ij = col_(i-j) below the diagonal and row_(j-i) above it.
+///
+/// Returns None if any leading principal submatrix of T is singular, in which
+/// case the system must be solved with [`Matrix::solve`] instead. Over a finite
+/// field this can happen even when T itself is nonsingular.
+pub fn solve_toeplitz<const M: u32>(col: &[Gfe<M>], row: &[Gfe<M>], b: &[Gfe<M>]) -> Option<Vec<Gfe<M>>> {
+	let n = check_lengths(col.len(), b.len());
+	check_lengths(n, row.len());
+	if n == 0 {
+		return Some(Vec::new());
+	}
+	if col[0] != row[0] {
+		panic!("The first column and row must share their first element")
+	}
+	// T_ij for i >= j and i <= j
+	let below = |i: usize, j: usize| col[i - j];
+	let above = |i: usize, j: usize| row[j - i];
+
+	if col[0] == Gfe::zero() {
+		return None;
+	}
+	// Forward and backward vectors with T f = e_0 and T g = e_last for the leading
+	// submatrix of each size, and the solution x of its system
+	let mut f = vec![col[0].inverse()];
+	let mut g = f.clone();
+	let mut x = vec![b[0] * col[0].inverse()];
+	for (size, &y) in b.iter().enumerate().skip(1) {
+		// The errors from appending a zero: the last row of T [f, 0] and the first
+		// row of T [0, g]
+		let ef = (0..size).fold(Gfe::zero(), |acc, j| acc + below(size, j) * f[j]);
+		let eg = (0..size).fold(Gfe::zero(), |acc, j| acc + above(0, j + 1) * g[j]);
+		let denominator = Gfe::one() + (ef * eg).negation();
+		if denominator == Gfe::zero() {
+			return None;
+		}
+		let scale = denominator.inverse();
+
+		f.push(Gfe::zero());
+		g.insert(0, Gfe::zero());
+		let (f_prev, g_prev) = (f.clone(), g.clone());
+		for j in 0..=size {
+			f[j] = (f_prev[j] + (ef * g_prev[j]).negation()) * scale;
+			g[j] = (g_prev[j] + (eg * f_prev[j]).negation()) * scale;
+		}
+
+		let ex = (0..size).fold(Gfe::zero(), |acc, j| acc + below(size, j) * x[j]);
+		x.push(Gfe::zero());
+		let c = y + ex.negation();
+		for j in 0..=size {
+			x[j] = x[j] + c * g[j];
+		}
+	}
+	Some(x)
+}
+
+/// Solve H x = b in O(n^2) operations, where H is the Hankel matrix with
+/// H_ij = h_(i+j) for h of length 2n - 1, such as the syndrome matrix of a
+/// Reed-Solomon code. Returns None in the same cases as [`solve_toeplitz`].
+pub fn solve_hankel<const M: u32>(h: &[Gfe<M>], b: &[Gfe<M>]) -> Option<Vec<Gfe<M>>> {
+	let n = b.len();
+	if h.len() != (2 * n).saturating_sub(1) {
+		panic!("Expected {} Hankel elements, got {}", (2 * n).saturating_sub(1), h.len())
+	}
+	if n == 0 {
+		return Some(Vec::new());
+	}
+	// Reversing the columns of H gives the Toeplitz matrix T_ij = h_(n-1+i-j)
+	let col = h[(n - 1)..].to_vec();
+	let row = h[..n].iter().rev().copied().collect::<Vec<_>>();
+	let mut x = solve_toeplitz(&col, &row, b)?;
+	x.reverse();
+	Some(x)
+}
+
+fn check_lengths(expected: usize, len: usize) -> usize {
+	if expected != len {
+		panic!("Expected vectors of length {expected}, got {len}")
+	}
+	len
+}
+
+/// Add c * src to dst element by element, reducing each element once
+fn add_c_slice_to<const M: u32>(src: &[Gfe<M>], dst: &mut [Gfe<M>], c: Gfe<M>) {
+	for (x, &y) in dst.iter_mut().zip(src) {
+		*x = x.mul_add(y, c);
+	}
+}
+
+/// The number of elements, rows times columns, from which [`Matrix::row_reduce`]
+/// updates the rows in parallel when the `rayon` feature is enabled. Each pivot
+/// then costs about 10µs more, which `benches/parallel_row_reduce.rs` shows is
+/// lost in the elimination itself only from around this size.
+pub const PARALLEL_ROW_REDUCE_ELEMS: usize = 1 << 16;
+
+/// Add the multiple of the given row to every other row of an n column matrix
+/// of unreduced u64 elements that zeroes out its element in the given column,
+/// where the row is reduced and has a one in that column. The products are added
+/// without reducing, so every element grows by less than (M - 1)^2. The rows are
+/// updated in parallel if `parallel` is set.
+fn eliminate<const M: u32>(work: &mut [u64], n: usize, row: usize, column: usize, parallel: bool) {
+	if n == 0 {
+		return;
+	}
+	let modulus = M as u64;
+	let pivot = work[row * n..(row + 1) * n].to_vec();
+	let update = |(i, other): (usize, &mut [u64])| {
+		let c = (modulus - other[column] % modulus) % modulus;
+		if i != row && c != 0 {
+			for (x, &y) in other.iter_mut().zip(&pivot) {
+				*x += c * y;
+			}
+		}
+	};
+
+	#[cfg(feature = "rayon")]
+	if parallel {
+		use rayon::prelude::*;
+		work.par_chunks_mut(n).enumerate().for_each(update);
+		return;
+	}
+	#[cfg(not(feature = "rayon"))]
+	debug_assert!(!parallel, "Parallel elimination needs the rayon feature");
+	work.chunks_mut(n).enumerate().for_each(update);
+}
+
+/// A factorization P A = L U of a nonsingular square matrix A, where P is a
+/// permutation matrix, L is lower triangular with ones on the diagonal and U is
+/// upper triangular. Created with [`Matrix::plu`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plu<const M: u32> {
+	/// L below the diagonal, whose diagonal of ones is not stored, and U on and
+	/// above it
+	lu: Matrix<M>,
+	/// Row i of P A is row perm[i] of A
+	perm: Vec<usize>,
+}
+
+impl<const M: u32> Plu<M> {
+	/// The number of rows and columns of A
+	pub fn n(&self) -> usize {
+		self.lu.n
+	}
+
+	/// Solve A x = b
+	pub fn solve(&self, b: &[Gfe<M>]) -> Vec<Gfe<M>> {
+		if b.len() != self.n() {
+			panic!("Expected a right hand side of length {}, got {}", self.n(), b.len())
+		}
+		let permuted = self.perm.iter().map(|&i| b[i]).collect::<Vec<_>>();
+		self.back_substitute(self.forward_substitute(permuted))
+	}
+
+	/// Solve A X = B, treating each column of B as a right hand side
+	pub fn solve_matrix(&self, b: &Matrix<M>) -> Matrix<M> {
+		let columns = b.cols().map(|col| self.solve(&col.collect::<Vec<_>>())).collect::<Vec<_>>();
+		Matrix::from_fn(self.n(), b.n, |i, j| columns[j][i])
+	}
+
+	/// The factorization of the block matrix [[A, A12], [A21, A22]], reusing this
+	/// factorization of A. Only the Schur complement A22 - A21 A^-1 A12 is
+	/// factored from scratch, so adding b rows and columns to an n by n matrix
+	/// takes O(n^2 b + b^3) operations. Returns None if the block matrix is
+	/// singular.
+	pub fn extend(&self, a12: &Matrix<M>, a21: &Matrix<M>, a22: &Matrix<M>) -> Option<Plu<M>> {
+		let (n, b) = (self.n(), a22.n);
+		if (a12.m, a12.n, a21.m, a21.n, a22.m) != (n, b, b, n, b) {
+			panic!(
+				"Blocks of size {}x{}, {}x{} and {}x{} do not extend a {n}x{n} matrix",
+				a12.m, a12.n, a21.m, a21.n, a22.m, a22.n
+			)
+		}
+
+		// U12 = L^-1 P A12, L21 = A21 U^-1 and S = A22 - L21 U12
+		let u12 = a12
+			.cols()
+			.map(|col| {
+				let col = col.collect::<Vec<_>>();
+				self.forward_substitute(self.perm.iter().map(|&i| col[i]).collect())
+			})
+			.collect::<Vec<_>>();
+		let l21 = a21.rows().map(|row| self.solve_upper_transpose(row)).collect::<Vec<_>>();
+		let schur = Matrix::from_fn(b, b, |i, j| a22.elem(i, j) + Gfe::dot(&l21[i], &u12[j]).negation());
+		let Plu { lu: lu22, perm: perm22 } = schur.plu()?;
+
+		// With S = P2^T L22 U22, the block matrix is factored by P = diag(P, P2),
+		// L = [[L, 0], [P2 L21, L22]] and U = [[U, U12], [0, U22]]
+		let lu = Matrix::from_fn(n + b, n + b, |i, j| match (i < n, j < n) {
+			(true, true) => self.lu.elem(i, j),
+			(true, false) => u12[j - n][i],
+			(false, true) => l21[perm22[i - n]][j],
+			(false, false) => lu22.elem(i - n, j - n),
+		});
+		let perm = self.perm.iter().copied().chain(perm22.iter().map(|&i| i + n)).collect();
+		Some(Plu { lu, perm })
+	}
+
+	/// The permutation matrix P
+	pub fn p(&self) -> Matrix<M> {
+		Matrix::from_fn(self.n(), self.n(), |i, j| if self.perm[i] == j { Gfe::one() } else { Gfe::zero() })
+	}
+
+	/// The lower triangular factor L, which has ones on its diagonal
+	pub fn l(&self) -> Matrix<M> {
+		Matrix::from_fn(self.n(), self.n(), |i, j| match i.cmp(&j) {
+			std::cmp::Ordering::Greater => self.lu.elem(i, j),
+			std::cmp::Ordering::Equal => Gfe::one(),
+			std::cmp::Ordering::Less => Gfe::zero(),
+		})
+	}
+
+	/// The upper triangular factor U
+	pub fn u(&self) -> Matrix<M> {
+		Matrix::from_fn(self.n(), self.n(), |i, j| if i <= j { self.lu.elem(i, j) } else { Gfe::zero() })
+	}
+
+	/// The determinant of A, which is the product of the diagonal of U with the
+	/// sign of the permutation
+	pub fn determinant(&self) -> Gfe<M> {
+		let mut det = (0..self.n()).fold(Gfe::one(), |acc, i| acc * self.lu.elem(i, i));
+		// Each cycle of length l is made of l - 1 swaps
+		let mut visited = vec![false; self.n()];
+		for start in 0..self.n() {
+			let mut i = start;
+			let mut len = 0;
+			while !visited[i] {
+				visited[i] = true;
+				i = self.perm[i];
+				len += 1;
+			}
+			if len > 0 && len % 2 == 0 {
+				det = det.negation();
+			}
+		}
+		det
+	}
+
+	/// Solve L y = b
+	fn forward_substitute(&self, mut y: Vec<Gfe<M>>) -> Vec<Gfe<M>> {
+		for i in 0..self.n() {
+			y[i] = y[i] + Gfe::dot(&self.lu.row(i)[..i], &y[..i]).negation();
+		}
+		y
+	}
+
+	/// Solve U x = y
+	fn back_substitute(&self, mut x: Vec<Gfe<M>>) -> Vec<Gfe<M>> {
+		for i in (0..self.n()).rev() {
+			let row = self.lu.row(i);
+			x[i] = (x[i] + Gfe::dot(&row[(i + 1)..], &x[(i + 1)..]).negation()) * row[i].inverse();
+		}
+		x
+	}
+
+	/// Solve x U = a for the row vector x
+	fn solve_upper_transpose(&self, a: &[Gfe<M>]) -> Vec<Gfe<M>> {
+		let mut x = a.to_vec();
+		for j in 0..self.n() {
+			let sum = (0..j).fold(Gfe::zero(), |acc, i| acc + x[i] * self.lu.elem(i, j));
+			x[j] = (x[j] + sum.negation()) * self.lu.elem(j, j).inverse();
+		}
+		x
+	}
+}
+
+/// The solutions of a linear system A x = b
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Solution<const M: u32> {
+	/// There is exactly one solution
+	Unique(Vec<Gfe<M>>),
+	/// The solutions are the particular solution plus any linear combination of
+	/// the nullspace basis vectors, which are the solutions of A x = 0
+	Parametric {
+		particular: Vec<Gfe<M>>,
+		nullspace: Vec<Vec<Gfe<M>>>,
+	},
+	/// There are no solutions
+	Inconsistent,
+}
+
+#[cfg(test)]
+fn gfe_matrix<const M: u32>(rows: &[&[i64]]) -> Matrix<M> {
+	Matrix::from_rows(
+		&rows
+			.iter()
+			.map(|row| row.iter().map(|&x| Gfe::from(x)).collect::<Vec<_>>())
+			.collect::<Vec<_>>(),
+	)
 }
 
 #[test]
 fn test_row_reduce_non_square_non_trivial() {
-	use crate::field::Gfe19;
-	#[rustfmt::skip]
-	let elems = [
-		1, 2, 0, 1, -1, 6,
-		1, 3, 0, 1, 2, 2,
-		0, 3, 0, 2, 5, 3,
-		2, 5, 0, 2, 1, 8,
-		3, 2, 0, 6, -4, 3,
-	]
-		.into_iter()
-		.map(|x| Gfe19::from(x))
-		.collect::<Vec<_>>();
-	let mut matrix = Matrix { m: 5, n: 6, elems };
+	let mut matrix = gfe_matrix::<19>(&[
+		&[1, 2, 0, 1, -1, 6],
+		&[1, 3, 0, 1, 2, 2],
+		&[0, 3, 0, 2, 5, 3],
+		&[2, 5, 0, 2, 1, 8],
+		&[3, 2, 0, 6, -4, 3],
+	]);
 	matrix.row_reduce();
 	println!("{matrix}");
 }
 
+#[cfg(feature = "rayon")]
+#[test]
+fn test_row_reduce_parallel() {
+	use crate::test::{M, TRIALS};
+	use rand::Rng;
+	let mut rng = rand::thread_rng();
+	for _ in 0..TRIALS {
+		let (m, n) = (rng.gen_range(1..=60), rng.gen_range(1..=60));
+		let mut serial = Matrix::<M>::from_fn(m, n, |_, _| Gfe::new(rng.gen_range(0..M)));
+		// Repeat a row so that not every column has a pivot
+		if m > 1 {
+			let row = serial.row(0).to_vec();
+			serial.row_mut(m - 1).copy_from_slice(&row);
+		}
+		let mut parallel = serial.clone();
+		serial.row_reduce_with(false);
+		parallel.row_reduce_with(true);
+		assert_eq!(parallel, serial);
+	}
+}
+
 #[test]
 fn test_row_reduce_inconsistent() {
-	use crate::field::Gfe19;
-	#[rustfmt::skip]
-	let elems = [
-		1, 2, 3, 5,
-		1, 2, 3, 6
-	]
-		.into_iter().map(|x| Gfe19::from(x)).collect::<Vec<_>>();
-	let mut matrix = Matrix { m: 2, n: 4, elems };
+	let mut matrix = gfe_matrix::<19>(&[&[1, 2, 3, 5], &[1, 2, 3, 6]]);
 	matrix.row_reduce();
 	println!("{matrix}");
 }
 
+#[test]
+fn test_solve() {
+	// x + 2y = 5, 3x + 4y = 6
+	let a = gfe_matrix::<19>(&[&[1, 2], &[3, 4]]);
+	let (solution, pivots) = a.solve(&gfe_vec(&[5, 6]));
+	assert_eq!(solution, Solution::Unique(gfe_vec(&[15, 14])));
+	assert_eq!(pivots, vec![0, 1]);
+
+	// x + 2y + 3z = 5 twice, and a row of zeros
+	let augmented = gfe_matrix::<19>(&[&[1, 2, 3, 5], &[2, 4, 6, 10], &[0, 0, 0, 0]]);
+	let (solution, pivots) = augmented.solve_augmented();
+	assert_eq!(
+		solution,
+		Solution::Parametric {
+			particular: gfe_vec(&[5, 0, 0]),
+			nullspace: vec![gfe_vec(&[-2, 1, 0]), gfe_vec(&[-3, 0, 1])],
+		}
+	);
+	assert_eq!(pivots, vec![0]);
+
+	let (solution, pivots) = gfe_matrix::<19>(&[&[1, 2, 3, 5], &[1, 2, 3, 6]]).solve_augmented();
+	assert_eq!(solution, Solution::Inconsistent);
+	assert_eq!(pivots, vec![0]);
+}
+
+#[test]
+fn test_rank_nullspace_transpose() {
+	let a = gfe_matrix::<19>(&[&[1, 2, 0, 1], &[2, 4, 1, 0], &[3, 6, 1, 1]]);
+	assert_eq!(a.rank(), 2);
+	assert_eq!(a.transpose().rank(), 2);
+	assert_eq!(a.transpose().transpose(), a);
+	assert_eq!(a.transpose()[(3, 1)], Gfe::zero());
+	assert_eq!(a.rref(), gfe_matrix(&[&[1, 2, 0, 1], &[0, 0, 1, -2], &[0, 0, 0, 0]]));
+
+	let nullspace = a.nullspace();
+	assert_eq!(nullspace.len(), a.n() - a.rank());
+	for x in nullspace {
+		assert!((&a * &x[..]).iter().all(|&y| y == Gfe::zero()));
+	}
+	assert!(gfe_matrix::<19>(&[&[1, 2], &[3, 4]]).nullspace().is_empty());
+}
+
+#[test]
+fn test_determinant_inverse() {
+	let a = gfe_matrix::<19>(&[&[0, 2, 1], &[1, 1, 0], &[3, 0, 5]]);
+	// 0 * 5 - 2 * 5 + 1 * -3
+	assert_eq!(a.determinant(), Gfe::from(-13));
+	let inverse = a.inverse().unwrap();
+	assert_eq!(&a * &inverse, Matrix::identity(3));
+	assert_eq!(inverse.inverse().unwrap(), a);
+
+	let singular = gfe_matrix::<19>(&[&[1, 2], &[2, 4]]);
+	assert_eq!(singular.determinant(), Gfe::zero());
+	assert!(singular.inverse().is_none());
+}
+
+#[test]
+fn test_construction_and_indexing() {
+	let mut a = Matrix::<19>::zeros(2, 3);
+	a[(1, 2)] = Gfe::new(7);
+	assert_eq!(a, Matrix::from_fn(2, 3, |i, j| Gfe::from(if (i, j) == (1, 2) { 7 } else { 0 })));
+	assert_eq!(a.row(1), &gfe_vec::<19>(&[0, 0, 7])[..]);
+	assert_eq!(a.col(2).collect::<Vec<_>>(), gfe_vec(&[0, 7]));
+	assert_eq!(a.rows().count(), 2);
+	assert_eq!(a.cols().count(), 3);
+	assert_eq!(a.submatrix(1..2, 1..3), gfe_matrix(&[&[0, 7]]));
+
+	let v = Matrix::vandermonde(&gfe_vec::<19>(&[2, 3]), 3);
+	assert_eq!(v, gfe_matrix(&[&[1, 2, 4], &[1, 3, 9]]));
+	assert_eq!(Matrix::<19>::from_rows::<Vec<_>>(&[]), Matrix::zeros(0, 0));
+}
+
+#[test]
+fn test_arithmetic() {
+	let a = gfe_matrix::<19>(&[&[1, 2], &[3, 4], &[5, 6]]);
+	let b = gfe_matrix::<19>(&[&[1, 0, 2], &[0, 1, 3]]);
+	assert_eq!(&a * &b, gfe_matrix(&[&[1, 2, 8], &[3, 4, 18], &[5, 6, 28]]));
+	assert_eq!(&a * &Matrix::identity(2), a);
+	assert_eq!(a.clone() + a.clone(), gfe_matrix(&[&[2, 4], &[6, 8], &[10, 12]]));
+	assert_eq!(&a * &gfe_vec(&[1, 1])[..], gfe_vec(&[3, 7, 11]));
+}
+
+#[test]
+fn test_lazy_reduction() {
+	use crate::test::M;
+	use rand::Rng;
+	// Large enough that elements are reduced partway through, with elements as
+	// large as possible
+	let mut rng = rand::thread_rng();
+	let a = Matrix::<M>::from_fn(30, 31, |_, _| Gfe::new(rng.gen_range(0..M)));
+	let max = Matrix::<M>::from_fn(30, 30, |_, _| Gfe::new(M - 1));
+	let large = Matrix::<M>::from_fn(30, 30, |_, _| Gfe::new(rng.gen_range((M - 100)..M)));
+
+	// Products against sums of element-wise products
+	for (x, y) in [(&a, &a.transpose()), (&max, &max)] {
+		let expected = Matrix::from_fn(x.m, y.n, |i, j| {
+			(0..x.n).fold(Gfe::zero(), |acc, l| acc + x.elem(i, l) * y.elem(l, j))
+		});
+		assert_eq!(x * y, expected);
+	}
+
+	// Row reduction against elimination one element at a time
+	for mut mat in [a, large] {
+		let mut expected = mat.clone();
+		let mut next_row = 0;
+		for column in 0..expected.n {
+			let Some(row) = (next_row..expected.m).find(|&row| expected.elem(row, column) != Gfe::zero()) else {
+				continue;
+			};
+			expected.swap_rows(row, next_row);
+			expected.mul_row(next_row, expected.elem(next_row, column).inverse());
+			for other in (0..expected.m).filter(|&other| other != next_row) {
+				expected.add_c_row_to(next_row, other, expected.elem(other, column).negation());
+			}
+			next_row += 1;
+		}
+		mat.row_reduce();
+		assert_eq!(mat, expected);
+	}
+}
+
+#[test]
+fn test_plu() {
+	let a = gfe_matrix::<19>(&[&[0, 2, 1, 4], &[1, 1, 0, 3], &[3, 0, 5, 1], &[2, 2, 0, 7]]);
+	let plu = a.plu().unwrap();
+	assert_eq!(&plu.p() * &a, &plu.l() * &plu.u());
+	assert_eq!(plu.determinant(), a.determinant());
+
+	for b in [gfe_vec(&[1, 2, 3, 4]), gfe_vec(&[0, 0, 0, 1]), gfe_vec(&[18, 5, 0, 9])] {
+		let x = plu.solve(&b);
+		assert_eq!(&a * &x[..], b);
+	}
+	let b = gfe_matrix::<19>(&[&[1, 0], &[2, 5], &[3, 0], &[4, 9]]);
+	assert_eq!(&a * &plu.solve_matrix(&b), b);
+
+	assert!(gfe_matrix::<19>(&[&[1, 2], &[2, 4]]).plu().is_none());
+}
+
+#[test]
+fn test_plu_extend() {
+	// A Vandermonde block shared by several systems that differ in their other
+	// columns and rows, like the Berlekamp-Welch system for different messages
+	let xs = gfe_vec::<19>(&[0, 1, 2, 3, 4]);
+	let v = Matrix::vandermonde(&xs, 5);
+	let a = v.submatrix(0..3, 0..3);
+	let plu = a.plu().unwrap();
+	for seed in 1..5 {
+		let a12 = Matrix::from_fn(3, 2, |i, j| Gfe::from((seed * (i + 2 * j) + 1) as i64));
+		let a21 = v.submatrix(3..5, 0..3);
+		let a22 = Matrix::from_fn(2, 2, |i, j| Gfe::from((seed * seed + i * 3 + j) as i64));
+		let full = Matrix::from_fn(5, 5, |i, j| match (i < 3, j < 3) {
+			(true, true) => a.elem(i, j),
+			(true, false) => a12.elem(i, j - 3),
+			(false, true) => a21.elem(i - 3, j),
+			(false, false) => a22.elem(i - 3, j - 3),
+		});
+
+		let Some(extended) = plu.extend(&a12, &a21, &a22) else {
+			assert!(full.inverse().is_none());
+			continue;
+		};
+		assert_eq!(&extended.p() * &full, &extended.l() * &extended.u());
+		let b = gfe_vec(&[1, 2, 3, 4, 5]);
+		assert_eq!(&full * &extended.solve(&b)[..], b);
+		assert_eq!(extended.determinant(), full.determinant());
+	}
+}
+
+#[test]
+fn test_structured_solvers() {
+	let b = gfe_vec::<19>(&[3, 1, 4, 1, 5]);
+
+	let xs = gfe_vec::<19>(&[2, 7, 0, 11, 18]);
+	let a = solve_vandermonde(&xs, &b).unwrap();
+	assert_eq!(&Matrix::vandermonde(&xs, 5) * &a[..], b);
+	assert_eq!(solve_vandermonde(&gfe_vec::<19>(&[2, 7, 2, 11, 18]), &b), None);
+
+	let ys = gfe_vec::<19>(&[1, 3, 4, 5, 6]);
+	let cauchy = Matrix::from_fn(5, 5, |i, j| (xs[i] + ys[j].negation()).inverse());
+	let a = solve_cauchy(&xs, &ys, &b).unwrap();
+	assert_eq!(&cauchy * &a[..], b);
+	assert_eq!(solve_cauchy(&xs, &gfe_vec::<19>(&[1, 3, 4, 5, 7]), &b), None);
+
+	let col = gfe_vec::<19>(&[5, 1, 2, 8, 3]);
+	let row = gfe_vec::<19>(&[5, 9, 4, 0, 6]);
+	let toeplitz = Matrix::from_fn(5, 5, |i, j| if i >= j { col[i - j] } else { row[j - i] });
+	let x = solve_toeplitz(&col, &row, &b).unwrap();
+	assert_eq!(&toeplitz * &x[..], b);
+	// Nonsingular, but with a zero in the corner
+	let zero_corner = gfe_vec::<19>(&[0, 1]);
+	assert_eq!(solve_toeplitz(&zero_corner, &zero_corner, &b[..2]), None);
+
+	let h = gfe_vec::<19>(&[2, 7, 1, 8, 2, 8, 1, 8, 4]);
+	let hankel = Matrix::from_fn(5, 5, |i, j| h[i + j]);
+	let x = solve_hankel(&h, &b).unwrap();
+	assert_eq!(&hankel * &x[..], b);
+	// Changing the last element to 3 makes H singular
+	let singular = gfe_vec::<19>(&[2, 7, 1, 8, 2, 8, 1, 8, 3]);
+	assert_eq!(Matrix::from_fn(5, 5, |i, j| singular[i + j]).determinant(), Gfe::zero());
+	assert_eq!(solve_hankel(&singular, &b), None);
+	assert_eq!(solve_hankel::<19>(&[], &[]), Some(Vec::new()));
+}
+
 impl<const M: u32> Display for Matrix<M> {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		if self.m * self.n == 0 {
@@ -122,7 +1099,7 @@ impl<const M: u32> Display for Matrix<M> {
 				}
 				write!(f, "{buf} ")?;
 			}
-			write!(f, " |\n")?;
+			writeln!(f, " |")?;
 		}
 
 		Ok(())
@@ -0,0 +1,393 @@
+use std::{
+	cmp::Ordering,
+	collections::BTreeMap,
+	fmt::Display,
+	ops::{Add, Mul, Sub},
+};
+
+use crate::{field::Gfe, polynomial::Polynomial};
+
+/// A polynomial over GF(M) in a fixed number of variables x_0, x_1, ...
+///
+/// Each term is keyed by its exponent vector, which has one entry per
+/// variable. Terms with a zero coefficient are never stored, so the zero
+/// polynomial has no terms.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MultivariatePolynomial<const M: u32> {
+	vars: usize,
+	terms: BTreeMap<Vec<usize>, Gfe<M>>,
+}
+
+/// An ordering of monomials, used to pick leading terms
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MonomialOrder {
+	/// Lexicographic on the exponents, with x_0 the most significant
+	Lex,
+	/// Total degree, ties broken by [`MonomialOrder::Lex`]
+	GradedLex,
+	/// Total degree, ties broken by the smaller exponent of the last variable
+	/// where the monomials differ
+	GradedReverseLex,
+	/// Degree weighted by the given per-variable weights, ties broken by
+	/// [`MonomialOrder::Lex`]. With weights (1, k-1) this is the order used for
+	/// bivariate interpolation in list decoding.
+	Weighted(Vec<usize>),
+}
+
+impl MonomialOrder {
+	/// Compare two exponent vectors of the same length under this ordering
+	pub fn compare(&self, a: &[usize], b: &[usize]) -> Ordering {
+		let total = |e: &[usize]| e.iter().sum::<usize>();
+		match self {
+			MonomialOrder::Lex => a.cmp(b),
+			MonomialOrder::GradedLex => total(a).cmp(&total(b)).then_with(|| a.cmp(b)),
+			MonomialOrder::GradedReverseLex => total(a).cmp(&total(b)).then_with(|| {
+				// The monomial with the smaller exponent in the last differing variable is larger
+				match a.iter().zip(b).rev().find(|(x, y)| x != y) {
+					Some((x, y)) => y.cmp(x),
+					None => Ordering::Equal,
+				}
+			}),
+			MonomialOrder::Weighted(weights) => weighted(weights, a).cmp(&weighted(weights, b)).then_with(|| a.cmp(b)),
+		}
+	}
+}
+
+fn weighted(weights: &[usize], e: &[usize]) -> usize {
+	weights.iter().zip(e).map(|(w, e)| w * e).sum()
+}
+
+impl<const M: u32> MultivariatePolynomial<M> {
+	/// Construct a polynomial in `vars` variables from (exponents, coefficient)
+	/// terms. Terms with the same exponents are added together. Panics if an
+	/// exponent vector does not have one entry per variable.
+	pub fn new(vars: usize, terms: Vec<(Vec<usize>, Gfe<M>)>) -> Self {
+		let mut p = Self::zero(vars);
+		for (e, c) in terms {
+			p.add_term(e, c);
+		}
+		p
+	}
+
+	pub fn zero(vars: usize) -> Self {
+		Self {
+			vars,
+			terms: BTreeMap::new(),
+		}
+	}
+
+	pub fn constant(vars: usize, c: Gfe<M>) -> Self {
+		Self::new(vars, vec![(vec![0; vars], c)])
+	}
+
+	/// The polynomial x_i
+	pub fn variable(vars: usize, i: usize) -> Self {
+		let mut e = vec![0; vars];
+		e[i] = 1;
+		Self::new(vars, vec![(e, Gfe::one())])
+	}
+
+	/// Embed a univariate polynomial as a polynomial in variable `var`
+	pub fn from_univariate(vars: usize, var: usize, p: &Polynomial<M>) -> Self {
+		let terms = p.coeffs().iter().enumerate().map(|(d, &c)| {
+			let mut e = vec![0; vars];
+			e[var] = d;
+			(e, c)
+		});
+		Self::new(vars, terms.collect())
+	}
+
+	fn add_term(&mut self, e: Vec<usize>, c: Gfe<M>) {
+		assert_eq!(e.len(), self.vars, "Exponent vector length must match the number of variables");
+		let sum = self.terms.get(&e).copied().unwrap_or(Gfe::zero()) + c;
+		if sum == Gfe::zero() {
+			self.terms.remove(&e);
+		} else {
+			self.terms.insert(e, sum);
+		}
+	}
+
+	pub fn vars(&self) -> usize {
+		self.vars
+	}
+
+	/// The nonzero terms of this polynomial in lexicographic order
+	pub fn terms(&self) -> impl Iterator<Item = (&[usize], Gfe<M>)> {
+		self.terms.iter().map(|(e, &c)| (e.as_slice(), c))
+	}
+
+	/// The nonzero terms of this polynomial from largest to smallest under the given ordering
+	pub fn terms_in_order(&self, order: &MonomialOrder) -> Vec<(&[usize], Gfe<M>)> {
+		let mut terms = self.terms().collect::<Vec<_>>();
+		terms.sort_by(|a, b| order.compare(b.0, a.0));
+		terms
+	}
+
+	/// The largest term under the given ordering, or None for the zero polynomial
+	pub fn leading_term(&self, order: &MonomialOrder) -> Option<(&[usize], Gfe<M>)> {
+		self.terms().max_by(|a, b| order.compare(a.0, b.0))
+	}
+
+	/// The coefficient of the monomial with the given exponents
+	pub fn coeff(&self, e: &[usize]) -> Gfe<M> {
+		self.terms.get(e).copied().unwrap_or(Gfe::zero())
+	}
+
+	pub fn is_zero(&self) -> bool {
+		self.terms.is_empty()
+	}
+
+	/// The largest total degree of any term, or None for the zero polynomial
+	pub fn total_degree(&self) -> Option<usize> {
+		self.weighted_degree(&vec![1; self.vars])
+	}
+
+	/// The largest weighted degree sum_i w_i e_i of any term, or None for the
+	/// zero polynomial
+	pub fn weighted_degree(&self, weights: &[usize]) -> Option<usize> {
+		assert_eq!(weights.len(), self.vars);
+		self.terms.keys().map(|e| weighted(weights, e)).max()
+	}
+
+	/// The largest exponent of variable `var`, or None for the zero polynomial
+	pub fn degree_in(&self, var: usize) -> Option<usize> {
+		self.terms.keys().map(|e| e[var]).max()
+	}
+
+	pub fn eval(&self, x: &[Gfe<M>]) -> Gfe<M> {
+		assert_eq!(x.len(), self.vars);
+		// powers[i][d] = x_i^d for every exponent of x_i that appears
+		let powers = x
+			.iter()
+			.enumerate()
+			.map(|(i, &x)| {
+				let mut table = vec![Gfe::one()];
+				for _ in 0..self.degree_in(i).unwrap_or(0) {
+					table.push(*table.last().unwrap() * x);
+				}
+				table
+			})
+			.collect::<Vec<_>>();
+		let mut y = Gfe::zero();
+		for (e, &c) in &self.terms {
+			let monomial = e.iter().zip(&powers).fold(Gfe::one(), |acc, (&d, table)| acc * table[d]);
+			y = y + c * monomial;
+		}
+		y
+	}
+
+	/// The coefficient of x_var^j, as a polynomial in the same variables that
+	/// does not involve x_var
+	pub fn coefficient_of(&self, var: usize, j: usize) -> Self {
+		let terms = self.terms().filter(|(e, _)| e[var] == j).map(|(e, c)| {
+			let mut e = e.to_vec();
+			e[var] = 0;
+			(e, c)
+		});
+		Self::new(self.vars, terms.collect())
+	}
+
+	/// Convert to a univariate polynomial in x_var, or None if another variable
+	/// appears
+	pub fn to_univariate(&self, var: usize) -> Option<Polynomial<M>> {
+		let mut coeffs = vec![Gfe::zero(); self.degree_in(var).map_or(0, |d| d + 1)];
+		for (e, c) in self.terms() {
+			if e.iter().enumerate().any(|(i, &d)| i != var && d != 0) {
+				return None;
+			}
+			coeffs[e[var]] = c;
+		}
+		Some(Polynomial::new(coeffs))
+	}
+
+	/// Split into univariate polynomials in x_var, one for each combination of
+	/// the exponents of the other variables. For a bivariate Q(x, y) and var = 0
+	/// this gives the Q_j(x) such that Q(x, y) = sum_j Q_j(x) y^j, keyed by the
+	/// exponent vector with x's exponent set to 0.
+	pub fn univariate_slices(&self, var: usize) -> BTreeMap<Vec<usize>, Polynomial<M>> {
+		let mut slices = BTreeMap::<Vec<usize>, Vec<Gfe<M>>>::new();
+		for (e, c) in self.terms() {
+			let mut key = e.to_vec();
+			key[var] = 0;
+			let coeffs = slices.entry(key).or_default();
+			if coeffs.len() <= e[var] {
+				coeffs.resize(e[var] + 1, Gfe::zero());
+			}
+			coeffs[e[var]] = c;
+		}
+		slices.into_iter().map(|(key, coeffs)| (key, Polynomial::new(coeffs))).collect()
+	}
+
+	pub fn negation(&self) -> Self {
+		Self {
+			vars: self.vars,
+			terms: self.terms.iter().map(|(e, c)| (e.clone(), c.negation())).collect(),
+		}
+	}
+}
+
+impl<const M: u32> Add for &'_ MultivariatePolynomial<M> {
+	type Output = MultivariatePolynomial<M>;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		assert_eq!(self.vars, rhs.vars);
+		let mut sum = self.clone();
+		for (e, c) in rhs.terms() {
+			sum.add_term(e.to_vec(), c);
+		}
+		sum
+	}
+}
+
+impl<const M: u32> Add for MultivariatePolynomial<M> {
+	type Output = MultivariatePolynomial<M>;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		&self + &rhs
+	}
+}
+
+impl<const M: u32> Sub for &'_ MultivariatePolynomial<M> {
+	type Output = MultivariatePolynomial<M>;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		assert_eq!(self.vars, rhs.vars);
+		let mut difference = self.clone();
+		for (e, c) in rhs.terms() {
+			difference.add_term(e.to_vec(), c.negation());
+		}
+		difference
+	}
+}
+
+impl<const M: u32> Sub for MultivariatePolynomial<M> {
+	type Output = MultivariatePolynomial<M>;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		&self - &rhs
+	}
+}
+
+impl<const M: u32> Mul for &'_ MultivariatePolynomial<M> {
+	type Output = MultivariatePolynomial<M>;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		assert_eq!(self.vars, rhs.vars);
+		let mut product = MultivariatePolynomial::zero(self.vars);
+		for (e1, c1) in self.terms() {
+			for (e2, c2) in rhs.terms() {
+				product.add_term(e1.iter().zip(e2).map(|(a, b)| a + b).collect(), c1 * c2);
+			}
+		}
+		product
+	}
+}
+
+impl<const M: u32> Mul for MultivariatePolynomial<M> {
+	type Output = MultivariatePolynomial<M>;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		&self * &rhs
+	}
+}
+
+impl<const M: u32> Display for MultivariatePolynomial<M> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.is_zero() {
+			return write!(f, "0");
+		}
+		for (i, (e, c)) in self.terms().enumerate() {
+			if i > 0 {
+				write!(f, " + ")?;
+			}
+			write!(f, "{c}")?;
+			for (var, &d) in e.iter().enumerate() {
+				if d > 0 {
+					write!(f, " x{var}")?;
+				}
+				if d > 1 {
+					write!(f, "^{d}")?;
+				}
+			}
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+fn gfe_terms<const M: u32>(terms: &[(&[usize], i64)]) -> Vec<(Vec<usize>, Gfe<M>)> {
+	terms.iter().map(|&(e, c)| (e.to_vec(), Gfe::from(c))).collect()
+}
+
+#[test]
+fn arithmetic_test() {
+	let x = MultivariatePolynomial::<19>::variable(2, 0);
+	let y = MultivariatePolynomial::<19>::variable(2, 1);
+	let one = MultivariatePolynomial::constant(2, Gfe::one());
+	// (x + y)(x - y) = x^2 - y^2
+	let p = &(&x + &y) * &(&x - &y);
+	assert_eq!(p, MultivariatePolynomial::new(2, gfe_terms(&[(&[2, 0], 1), (&[0, 2], -1)])));
+	assert_eq!(&p - &p, MultivariatePolynomial::zero(2));
+	assert_eq!((&p + &one).to_string(), "1 + 18 x1^2 + 1 x0^2");
+	assert_eq!(
+		MultivariatePolynomial::new(2, gfe_terms::<19>(&[(&[1, 1], 3), (&[1, 1], 16)])),
+		MultivariatePolynomial::zero(2)
+	);
+}
+
+#[test]
+fn degree_and_eval_test() {
+	// Q(x, y) = 3 + x^4 y + 2 y^3
+	let q = MultivariatePolynomial::<19>::new(2, gfe_terms(&[(&[0, 0], 3), (&[4, 1], 1), (&[0, 3], 2)]));
+	assert_eq!(q.total_degree(), Some(5));
+	assert_eq!(q.weighted_degree(&[1, 2]), Some(6));
+	assert_eq!(q.weighted_degree(&[1, 3]), Some(9));
+	assert_eq!(q.degree_in(1), Some(3));
+	assert_eq!(MultivariatePolynomial::<19>::zero(2).weighted_degree(&[1, 2]), None);
+	assert_eq!(q.eval(&[Gfe::new(2), Gfe::new(3)]), Gfe::from(3 + 16 * 3 + 2 * 27));
+}
+
+#[test]
+fn monomial_order_test() {
+	let (a, b, c) = (&[2, 0, 1][..], &[1, 2, 0][..], &[0, 0, 4][..]);
+	assert_eq!(MonomialOrder::Lex.compare(a, b), Ordering::Greater);
+	assert_eq!(MonomialOrder::Lex.compare(b, c), Ordering::Greater);
+	assert_eq!(MonomialOrder::GradedLex.compare(a, c), Ordering::Less);
+	assert_eq!(MonomialOrder::GradedLex.compare(a, b), Ordering::Greater);
+	assert_eq!(MonomialOrder::GradedReverseLex.compare(a, b), Ordering::Less);
+	assert_eq!(MonomialOrder::Weighted(vec![3, 1, 1]).compare(a, c), Ordering::Greater);
+
+	let q = MultivariatePolynomial::<19>::new(
+		3,
+		vec![(a.to_vec(), Gfe::new(1)), (b.to_vec(), Gfe::new(2)), (c.to_vec(), Gfe::new(3))],
+	);
+	assert_eq!(q.leading_term(&MonomialOrder::Lex), Some((a, Gfe::new(1))));
+	assert_eq!(q.leading_term(&MonomialOrder::GradedLex), Some((c, Gfe::new(3))));
+	let order = q
+		.terms_in_order(&MonomialOrder::GradedReverseLex)
+		.into_iter()
+		.map(|(e, _)| e)
+		.collect::<Vec<_>>();
+	assert_eq!(order, vec![c, b, a]);
+}
+
+#[test]
+fn univariate_test() {
+	let p = "1 + 2 x^3".parse::<Polynomial<19>>().unwrap();
+	let embedded = MultivariatePolynomial::from_univariate(2, 1, &p);
+	assert_eq!(embedded.to_univariate(1), Some(p.clone()));
+	assert_eq!(embedded.to_univariate(0), None);
+	assert_eq!(MultivariatePolynomial::<19>::zero(3).to_univariate(2), Some(Polynomial::zero()));
+
+	// Q(x, y) = (1 + x) + (2 x^2) y + y^2 + x y^2
+	let q = MultivariatePolynomial::<19>::new(
+		2,
+		gfe_terms(&[(&[0, 0], 1), (&[1, 0], 1), (&[2, 1], 2), (&[0, 2], 1), (&[1, 2], 1)]),
+	);
+	let slices = q.univariate_slices(0);
+	assert_eq!(slices.len(), 3);
+	assert_eq!(slices[&vec![0, 1]], "2 x^2".parse().unwrap());
+	assert_eq!(slices[&vec![0, 2]], "1 + x".parse().unwrap());
+	assert_eq!(q.coefficient_of(1, 2).to_univariate(0), Some("1 + x".parse().unwrap()));
+}
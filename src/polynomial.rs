@@ -1,24 +1,40 @@
 use std::{
 	cmp::max,
 	fmt::Display,
-	ops::{Add, Mul},
+	ops::{Add, Mul, Sub},
+	str::FromStr,
 };
 
-use crate::field::Gfe;
+use crate::{
+	field::{Gfe, Xorshift},
+	sparse_polynomial::SparsePolynomial,
+};
+#[cfg(test)]
+use crate::test::{gfe_vec, rand_poly};
 
-// TODO: Eq
-#[derive(Debug, Clone)]
+/// A polynomial over GF(M). The coefficients are stored lowest degree first
+/// and never have trailing zeros, so the zero polynomial has no coefficients
+/// and every polynomial has exactly one representation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Polynomial<const M: u32> {
-	pub coeffs: Vec<Gfe<M>>,
+	coeffs: Vec<Gfe<M>>,
 }
 
+/// Error returned when dividing by the zero polynomial
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DivisionByZero;
+
 impl<const M: u32> Polynomial<M> {
 	pub fn constant(c: Gfe<M>) -> Self {
-		Self { coeffs: vec![c] }
+		Self::new(vec![c])
 	}
 
 	pub fn zero() -> Self {
-		Self::constant(Gfe::zero())
+		Self { coeffs: Vec::new() }
+	}
+
+	pub fn one() -> Self {
+		Self::constant(Gfe::one())
 	}
 
 	pub fn single(c: Gfe<M>, d: usize) -> Self {
@@ -28,24 +44,116 @@ impl<const M: u32> Polynomial<M> {
 	}
 
 	pub fn eval(&self, x: Gfe<M>) -> Gfe<M> {
+		// Horner's rule
 		let mut y = Gfe::zero();
-		for i in 0..self.coeffs.len() {
-			y = y + self.coeffs[i] * x.power(i as i32);
+		for &c in self.coeffs.iter().rev() {
+			y = y * x + c;
 		}
 		y
 	}
 
+	/// Evaluate this polynomial at each of the given points.
+	///
+	/// When there are more points than coefficients and they are evenly spaced,
+	/// as they are for the evaluation domain of a code, the values are found with
+	/// a table of forward differences as in [`Polynomial::eval_range`]. Otherwise
+	/// the points are evaluated with Horner's rule a block at a time, so each pass
+	/// over the coefficients serves several points with independent chains of
+	/// multiply-adds.
+	pub fn eval_many(&self, xs: impl IntoIterator<Item = Gfe<M>>) -> Vec<Gfe<M>> {
+		let xs = xs.into_iter().collect::<Vec<_>>();
+		let d = self.degree().unwrap_or(0);
+		if xs.len() > d + 1 {
+			let step = xs[1] + xs[0].negation();
+			if xs.windows(2).all(|w| w[1] + w[0].negation() == step) {
+				return self.eval_progression(xs[0], step, xs.len());
+			}
+		}
+		self.eval_horner(&xs)
+	}
+
+	/// Evaluate this polynomial at the len consecutive points start, start + 1,
+	/// ..., start + len - 1.
+	///
+	/// After evaluating the first deg + 1 points directly, the values are found
+	/// by stepping a table of forward differences, which costs deg additions per
+	/// point and no multiplications.
+	pub fn eval_range(&self, start: Gfe<M>, len: usize) -> Vec<Gfe<M>> {
+		self.eval_progression(start, Gfe::one(), len)
+	}
+
+	/// Evaluate this polynomial at start + t * step for t < len with forward
+	/// differences. p(start + t * step) is a polynomial of the same degree in t,
+	/// so its forward differences in t behave as they do for consecutive points.
+	fn eval_progression(&self, start: Gfe<M>, step: Gfe<M>, len: usize) -> Vec<Gfe<M>> {
+		let d = self.degree().unwrap_or(0);
+		let mut points = Vec::with_capacity(len.min(d + 1));
+		let mut x = start;
+		for _ in 0..len.min(d + 1) {
+			points.push(x);
+			x = x + step;
+		}
+		if len <= d + 1 {
+			return self.eval_horner(&points);
+		}
+
+		// diffs[j] is the j-th forward difference at the current point, starting
+		// from the values at the first d + 1 points
+		let mut diffs = self.eval_horner(&points);
+		for j in 1..=d {
+			for t in (j..=d).rev() {
+				diffs[t] = diffs[t] + diffs[t - 1].negation();
+			}
+		}
+		let mut values = Vec::with_capacity(len);
+		values.push(diffs[0]);
+		while values.len() < len {
+			// Step to the next point. The d-th difference of a degree d polynomial is constant.
+			for j in 0..d {
+				diffs[j] = diffs[j] + diffs[j + 1];
+			}
+			values.push(diffs[0]);
+		}
+		values
+	}
+
+	/// Horner's rule at a block of points at a time
+	fn eval_horner(&self, xs: &[Gfe<M>]) -> Vec<Gfe<M>> {
+		const BLOCK: usize = 8;
+		let mut values = Vec::with_capacity(xs.len());
+		for block in xs.chunks(BLOCK) {
+			let mut ys = [Gfe::zero(); BLOCK];
+			for &c in self.coeffs.iter().rev() {
+				for (y, &x) in ys.iter_mut().zip(block) {
+					*y = c.mul_add(*y, x);
+				}
+			}
+			values.extend_from_slice(&ys[..block.len()]);
+		}
+		values
+	}
+
+	/// Construct a polynomial from its coefficients, lowest degree first.
+	/// Trailing zero coefficients are removed.
 	pub fn new(coeffs: Vec<Gfe<M>>) -> Self {
 		let mut coeffs = coeffs;
-		while coeffs.len() > 1 && coeffs.last() == Some(&Gfe::zero()) {
+		while coeffs.last() == Some(&Gfe::zero()) {
 			coeffs.pop();
 		}
-		if coeffs.len() == 0 {
-			coeffs.push(Gfe::zero());
-		}
 		Self { coeffs }
 	}
 
+	/// The coefficients of this polynomial, lowest degree first. Empty for the
+	/// zero polynomial, and otherwise the last coefficient is nonzero.
+	pub fn coeffs(&self) -> &[Gfe<M>] {
+		&self.coeffs
+	}
+
+	/// The coefficient of x^i, which is zero beyond the degree
+	pub fn coeff(&self, i: usize) -> Gfe<M> {
+		self.coeffs.get(i).copied().unwrap_or(Gfe::zero())
+	}
+
 	/// Given roots r_1, r_2, ..., r_k generate the polynomial
 	/// (x-r_1)(x-r_2)...(x-r_k). Roots may be duplicated and will generate a
 	/// polynomial with higher multiplicity roots.
@@ -88,14 +196,66 @@ impl<const M: u32> Polynomial<M> {
 		partial + Polynomial::constant(coeff) * helper
 	}
 
-	/// Divide this polynomial by another
-	pub fn divide(&self, divisor: &Self) -> (Self, Self) {
+	/// Find the shortest linear recurrence that generates the sequence s with the
+	/// Berlekamp-Massey algorithm. Returns the connection polynomial
+	/// C(x) = 1 + c_1 x + ... + c_L x^L and the length L of the recurrence, such
+	/// that s_i + c_1 s_(i-1) + ... + c_L s_(i-L) = 0 for every i >= L. C has
+	/// degree less than L when the recurrence does not involve the first terms.
+	pub fn berlekamp_massey(s: &[Gfe<M>]) -> (Self, usize) {
+		// The current connection polynomial, and the one from before the length last
+		// changed along with its discrepancy
+		let mut c = vec![Gfe::one()];
+		let mut b = vec![Gfe::one()];
+		let mut b_discrepancy = Gfe::one();
+		let mut len = 0;
+		// The number of terms since the length last changed
+		let mut shift = 1;
+		for i in 0..s.len() {
+			let discrepancy = (1..=len.min(c.len() - 1)).fold(s[i], |acc, j| acc + c[j] * s[i - j]);
+			if discrepancy == Gfe::zero() {
+				shift += 1;
+				continue;
+			}
+
+			// C(x) -= d / d_b * x^shift * B(x)
+			let previous = c.clone();
+			let coeff = discrepancy * b_discrepancy.inverse();
+			if c.len() < b.len() + shift {
+				c.resize(b.len() + shift, Gfe::zero());
+			}
+			for (j, &bj) in b.iter().enumerate() {
+				c[j + shift] = c[j + shift] + (coeff * bj).negation();
+			}
+			if 2 * len <= i {
+				len = i + 1 - len;
+				b = previous;
+				b_discrepancy = discrepancy;
+				shift = 1;
+			} else {
+				shift += 1;
+			}
+		}
+		(Polynomial::new(c), len)
+	}
+
+	/// Divide this polynomial by another, returning the quotient and remainder.
+	/// The remainder has a lower degree than the divisor.
+	pub fn divide(&self, divisor: &Self) -> Result<(Self, Self), DivisionByZero> {
+		if divisor.is_zero() {
+			return Err(DivisionByZero);
+		}
+		Ok(self.div_rem(divisor))
+	}
+
+	/// Divide by a divisor which is known to be nonzero
+	fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+		debug_assert!(!divisor.is_zero());
 		// Algorithm overview: Repeatedly subtract multiples of the divisor from the dividend until nothing remains.
 		// First subtract a multiple of the divisor such that the leading term of the dividend is removed.
 		// Continue until all terms in the dividend have been removed.
 
 		// The quotient being constructed
-		let mut quotient = Polynomial::constant(Gfe::zero());
+		let mut quotient = Polynomial::zero();
 		// The remaining amount of dividend
 		let mut dividend = self.clone();
 
@@ -103,16 +263,16 @@ impl<const M: u32> Polynomial<M> {
 		// (AKA while we can still remove terms of the dividend by subtracting multiples of the divisor)
 		while dividend.degree() >= divisor.degree() {
 			// Get the leading coefficient of the divisor and dividend
-			let divisor_leading_coeff = *divisor.coeffs.last().unwrap();
-			let dividend_leading_coeff = *dividend.coeffs.last().unwrap();
+			let divisor_leading_coeff = divisor.leading_coeff();
+			let dividend_leading_coeff = dividend.leading_coeff();
 			// Create a constant multiple of a power of x such that when multiplied by the divisor and subtracted from
 			// the dividend, the leading term of the dividend is removed.
 			let piece = Polynomial::single(
 				divisor_leading_coeff.inverse() * dividend_leading_coeff,
-				dividend.degree() - divisor.degree(),
+				dividend.coeffs.len() - divisor.coeffs.len(),
 			);
 			// Remove the leading term of the dividend by adding piece
-			dividend = &dividend + &(&piece * &divisor).negation();
+			dividend = &dividend + &(&piece * divisor).negation();
 			// Add piece to the final quotient
 			quotient = &quotient + &piece;
 		}
@@ -120,14 +280,334 @@ impl<const M: u32> Polynomial<M> {
 		(quotient, dividend)
 	}
 
-	pub fn degree(&self) -> usize {
-		self.coeffs.len() - 1
+	/// The degree of this polynomial, or None for the zero polynomial (whose
+	/// degree is -infinity). Since None orders before every Some, degrees can be
+	/// compared directly.
+	pub fn degree(&self) -> Option<usize> {
+		self.coeffs.len().checked_sub(1)
 	}
 
-	pub fn negation(&self) -> Self {
-		Self {
-			coeffs: self.coeffs.iter().map(|x| x.negation()).collect(),
+	pub fn is_zero(&self) -> bool {
+		self.coeffs.is_empty()
+	}
+
+	/// The coefficient of the highest power of x, or zero for the zero polynomial
+	pub fn leading_coeff(&self) -> Gfe<M> {
+		self.coeffs.last().copied().unwrap_or(Gfe::zero())
+	}
+
+	/// Scale this polynomial so that its leading coefficient is 1. The zero
+	/// polynomial is returned unchanged.
+	pub fn monic(&self) -> Self {
+		if self.is_zero() {
+			return self.clone();
+		}
+		Polynomial::constant(self.leading_coeff().inverse()) * self.clone()
+	}
+
+	/// The quotient of this polynomial by x^m, discarding the m lowest terms
+	fn div_x_pow(&self, m: usize) -> Self {
+		Polynomial::new(self.coeffs.iter().skip(m).copied().collect())
+	}
+
+	/// The monic greatest common divisor of this polynomial and another. The gcd
+	/// of two zero polynomials is zero.
+	pub fn gcd(&self, other: &Self) -> Self {
+		let (mut a, mut b) = (self.clone(), other.clone());
+		while !b.is_zero() {
+			let r = a.div_rem(&b).1;
+			a = b;
+			b = r;
 		}
+		a.monic()
+	}
+
+	/// Compute the monic greatest common divisor g of this polynomial and another
+	/// along with Bezout coefficients s and t such that s * self + t * other = g.
+	/// Returns (g, s, t).
+	pub fn extended_gcd(&self, other: &Self) -> (Self, Self, Self) {
+		let [[s, t], _] = gcd_matrix(self, other);
+		let g = &(&s * self) + &(&t * other);
+		if g.is_zero() {
+			return (g, s, t);
+		}
+		let c = Polynomial::constant(g.leading_coeff().inverse());
+		(&c * &g, &c * &s, &c * &t)
+	}
+
+	/// Compute the inverse of this polynomial modulo m, which exists when the two
+	/// are coprime.
+	pub fn inverse_mod(&self, m: &Self) -> Option<Self> {
+		if m.is_zero() {
+			return None;
+		}
+		let (g, s, _t) = self.div_rem(m).1.extended_gcd(m);
+		if g != Polynomial::one() {
+			return None;
+		}
+		Some(s.div_rem(m).1)
+	}
+
+	/// Half-GCD of a and b, where deg a > deg b. Returns the matrix R of the
+	/// Euclidean remainder sequence of a and b such that R * (a, b) = (c, d) are
+	/// the consecutive remainders with deg c >= ceil(deg a / 2) > deg d.
+	///
+	/// The recursion only looks at the top half of the coefficients at each level.
+	/// With the schoolbook multiplication used here the matrix products cost
+	/// O(n^2), so the whole recursion is O(n^2 log n) against O(n^2) for the
+	/// Euclidean algorithm, but the Euclidean algorithm allocates a quotient and
+	/// two cofactors per step and loses above a few hundred terms. Below
+	/// [`HALF_GCD_CROSSOVER`] the remainder sequence is walked directly instead.
+	/// `benches/gcd.rs` measures the two.
+	pub fn half_gcd(a: &Self, b: &Self) -> [[Self; 2]; 2] {
+		assert!(a.degree() > b.degree());
+
+		let m = a.degree().unwrap().div_ceil(2);
+		if b.degree() < Some(m) {
+			return identity_matrix();
+		}
+		if a.degree() < Some(HALF_GCD_CROSSOVER) {
+			return euclid_matrix(a, b, m);
+		}
+
+		// The quotients of the top halves agree with those of a and b for the first
+		// half of the remainder sequence.
+		let r = Polynomial::half_gcd(&a.div_x_pow(m), &b.div_x_pow(m));
+		let [c, d] = apply_matrix(&r, a, b);
+		if d.degree() < Some(m) {
+			return r;
+		}
+
+		// One step of the Euclidean algorithm to get below degree m
+		let (q, e) = c.div_rem(&d);
+		let r = mul_matrix(&quotient_matrix(&q), &r);
+		if e.degree() < Some(m) {
+			return r;
+		}
+
+		// Recurse on the top part of (d, e) to finish the remaining half
+		let l = 2 * m - d.degree().unwrap();
+		let s = Polynomial::half_gcd(&d.div_x_pow(l), &e.div_x_pow(l));
+		mul_matrix(&s, &r)
+	}
+
+	/// Compute self^e modulo m by repeated squaring
+	pub fn pow_mod(&self, e: u64, m: &Self) -> Self {
+		assert!(!m.is_zero(), "Modulus must be nonzero");
+		let mut result = Polynomial::one().div_rem(m).1;
+		let mut base = self.div_rem(m).1;
+		let mut e = e;
+		while e > 0 {
+			if e & 1 == 1 {
+				result = (&result * &base).div_rem(m).1;
+			}
+			base = (&base * &base).div_rem(m).1;
+			e >>= 1;
+		}
+		result
+	}
+
+	/// The formal derivative of this polynomial. Note that in GF(M) the
+	/// derivative of x^M is zero.
+	pub fn derivative(&self) -> Self {
+		let coeffs = self.coeffs.iter().enumerate().skip(1).map(|(i, &c)| Gfe::from(i as i64) * c);
+		Polynomial::new(coeffs.collect())
+	}
+
+	/// The composition p(q(x)) of this polynomial p with another polynomial q
+	pub fn compose(&self, q: &Self) -> Self {
+		// Horner's rule with polynomial multiplication
+		let mut result = Polynomial::zero();
+		for &c in self.coeffs.iter().rev() {
+			result = &(&result * q) + &Polynomial::constant(c);
+		}
+		result
+	}
+
+	/// The polynomial p(x + c)
+	pub fn shift(&self, c: Gfe<M>) -> Self {
+		// Taylor shift by repeated synthetic division, which avoids the
+		// polynomial multiplications of composing with x + c.
+		let mut coeffs = self.coeffs.clone();
+		let d = coeffs.len();
+		for i in 0..d {
+			for j in (i..(d - 1)).rev() {
+				coeffs[j] = coeffs[j] + c * coeffs[j + 1];
+			}
+		}
+		Polynomial::new(coeffs)
+	}
+
+	/// Find the distinct roots of this polynomial in GF(M), in ascending order.
+	///
+	/// The product of the linear factors is extracted as gcd(f, x^M - x), which is
+	/// then split into its roots with Cantor-Zassenhaus equal-degree splitting.
+	pub fn roots(&self) -> Vec<Gfe<M>> {
+		if self.degree() < Some(1) {
+			return Vec::new();
+		}
+
+		let f = self.monic();
+		let vanishing = SparsePolynomial::new(vec![(M as u64, Gfe::one()), (1, Gfe::one().negation())]);
+		let linear = f.gcd(&vanishing.rem_dense(&f).unwrap());
+		let mut roots = linear
+			.equal_degree_factors(1)
+			.into_iter()
+			.map(|factor| factor.coeffs[0].negation())
+			.collect::<Vec<_>>();
+		roots.sort();
+		roots
+	}
+
+	/// Find the distinct roots of this polynomial among the given candidates by
+	/// evaluating at each one (Chien search). This is much cheaper than
+	/// [`Polynomial::roots`] when the candidates are a small set such as the
+	/// evaluation domain of a code. Stops early once deg(f) roots are found.
+	pub fn roots_in(&self, candidates: impl IntoIterator<Item = Gfe<M>>) -> Vec<Gfe<M>> {
+		if self.is_zero() {
+			return candidates.into_iter().collect();
+		}
+
+		let mut roots = Vec::new();
+		for x in candidates {
+			if Some(roots.len()) == self.degree() {
+				break;
+			}
+			if self.eval(x) == Gfe::zero() && !roots.contains(&x) {
+				roots.push(x);
+			}
+		}
+		roots
+	}
+
+	/// Factor this polynomial into monic irreducible factors over GF(M), returned
+	/// with their multiplicities and sorted by degree then coefficients. The
+	/// leading coefficient is not included. Panics on the zero polynomial.
+	pub fn factor(&self) -> Vec<(Self, usize)> {
+		assert!(!self.is_zero(), "Cannot factor the zero polynomial");
+
+		let mut factors = Vec::new();
+		for (square_free, multiplicity) in self.monic().square_free_factors() {
+			for (part, d) in square_free.distinct_degree_factors() {
+				factors.extend(part.equal_degree_factors(d).into_iter().map(|factor| (factor, multiplicity)));
+			}
+		}
+		factors.sort_by(|a, b| (a.0.degree(), &a.0.coeffs).cmp(&(b.0.degree(), &b.0.coeffs)));
+		factors
+	}
+
+	/// Split a monic polynomial into square-free monic factors f_i such that the
+	/// polynomial is the product of f_i^i. Factors equal to 1 are omitted.
+	fn square_free_factors(&self) -> Vec<(Self, usize)> {
+		let one = Polynomial::one();
+		let mut factors = Vec::new();
+		if self.degree() == Some(0) {
+			return factors;
+		}
+
+		let mut c = self.gcd(&self.derivative());
+		let mut w = self.div_rem(&c).0;
+		let mut i = 1;
+		while w != one {
+			let y = w.gcd(&c);
+			let factor = w.div_rem(&y).0;
+			if factor != one {
+				factors.push((factor, i));
+			}
+			w = y;
+			c = c.div_rem(&w).0;
+			i += 1;
+		}
+
+		// What remains has a zero derivative so it is a polynomial in x^M, and in
+		// GF(M) it is the M-th power of the polynomial with the same coefficients
+		// in x.
+		if c != one {
+			let root = Polynomial::new(c.coeffs.iter().step_by(M as usize).copied().collect());
+			factors.extend(root.square_free_factors().into_iter().map(|(f, j)| (f, j * M as usize)));
+		}
+		factors
+	}
+
+	/// Split a monic square-free polynomial into the products of its irreducible
+	/// factors of each degree, returned as (product, degree) pairs.
+	fn distinct_degree_factors(&self) -> Vec<(Self, usize)> {
+		let one = Polynomial::one();
+		let x = Polynomial::single(Gfe::one(), 1);
+		let mut factors = Vec::new();
+		let mut f = self.clone();
+		// h = x^(M^d) mod f
+		let mut h = x.div_rem(&f).1;
+		let mut d = 1;
+		while f.degree() >= Some(2 * d) {
+			h = h.pow_mod(M as u64, &f);
+			let g = f.gcd(&(&h - &x));
+			if g != one {
+				f = f.div_rem(&g).0;
+				h = h.div_rem(&f).1;
+				factors.push((g, d));
+			}
+			d += 1;
+		}
+		if f != one {
+			let d = f.coeffs.len() - 1;
+			factors.push((f, d));
+		}
+		factors
+	}
+
+	/// Split a monic square-free polynomial whose irreducible factors all have
+	/// degree d into those factors (Cantor-Zassenhaus).
+	fn equal_degree_factors(&self, d: usize) -> Vec<Self> {
+		if self.degree() == Some(0) {
+			return Vec::new();
+		}
+		if self.degree() == Some(d) {
+			return vec![self.clone()];
+		}
+
+		// Fixed seed so that factoring is deterministic; any sequence of trial
+		// polynomials splits f with probability about 1/2 per attempt.
+		let mut rng = Xorshift::new(0);
+		let mut next = move || rng.next_gfe();
+
+		let one = Polynomial::one();
+		loop {
+			let a = Polynomial::new((1..self.coeffs.len()).map(|_| next()).collect());
+			if a.degree() < Some(1) {
+				continue;
+			}
+			let b = if M == 2 {
+				// Trace map a + a^2 + ... + a^(2^(d-1)) is 0 or 1 on each factor
+				let mut t = a.clone();
+				let mut sum = a.clone();
+				for _ in 1..d {
+					t = (&t * &t).div_rem(self).1;
+					sum = &sum + &t;
+				}
+				sum
+			} else {
+				// a^((M^d - 1) / 2) = (a^(1 + M + ... + M^(d-1)))^((M - 1) / 2) is +-1
+				// on each factor coprime to a
+				let mut t = a.div_rem(self).1;
+				let mut norm = t.clone();
+				for _ in 1..d {
+					t = t.pow_mod(M as u64, self);
+					norm = (&norm * &t).div_rem(self).1;
+				}
+				&norm.pow_mod((M as u64 - 1) / 2, self) - &one
+			};
+			let g = self.gcd(&b);
+			if g != one && g.degree() < self.degree() {
+				let mut factors = g.equal_degree_factors(d);
+				factors.extend(self.div_rem(&g).0.equal_degree_factors(d));
+				return factors;
+			}
+		}
+	}
+
+	pub fn negation(&self) -> Self {
+		Polynomial::new(self.coeffs.iter().map(|x| x.negation()).collect())
 	}
 }
 
@@ -159,6 +639,34 @@ impl<const M: u32> Add for Polynomial<M> {
 	}
 }
 
+impl<const M: u32> Sub for &'_ Polynomial<M> {
+	type Output = Polynomial<M>;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		let mut coeffs = Vec::new();
+		let r = max(self.coeffs.len(), rhs.coeffs.len());
+		for i in 0..r {
+			coeffs.push(Gfe::zero());
+			if i < self.coeffs.len() {
+				coeffs[i] = coeffs[i] + self.coeffs[i];
+			}
+			if i < rhs.coeffs.len() {
+				coeffs[i] = coeffs[i] + rhs.coeffs[i].negation();
+			}
+		}
+
+		Polynomial::new(coeffs)
+	}
+}
+
+impl<const M: u32> Sub for Polynomial<M> {
+	type Output = Polynomial<M>;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		&self - &rhs
+	}
+}
+
 impl<const M: u32> Mul for &'_ Polynomial<M> {
 	type Output = Polynomial<M>;
 
@@ -181,15 +689,180 @@ impl<const M: u32> Mul for Polynomial<M> {
 	}
 }
 
-impl<const M: u32> PartialEq for Polynomial<M> {
-	fn eq(&self, other: &Self) -> bool {
-		// TODO: ensure invariants are held such that this implementation is valid
-		self.coeffs == other.coeffs
+impl Display for DivisionByZero {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "division by the zero polynomial")
+	}
+}
+
+impl std::error::Error for DivisionByZero {}
+
+/// The largest degree accepted when building a polynomial from a form that
+/// doesn't store every coefficient, such as text or a
+/// [`SparsePolynomial`]. Polynomials are stored densely, so this bounds the
+/// allocation to 2^24 coefficients (64 MiB).
+pub const MAX_DENSE_DEGREE: usize = 1 << 24;
+
+/// Error returned when parsing a polynomial fails, holding the term that could
+/// not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePolynomialError {
+	pub term: String,
+}
+
+impl Display for ParsePolynomialError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "invalid polynomial term `{}`", self.term)
 	}
 }
 
+impl std::error::Error for ParsePolynomialError {}
+
+/// Parse a sum of terms in x, such as the `3 + 2 x + 5 x^2` produced by
+/// [`Display`]. Terms may be in any order, repeated, or subtracted, and the
+/// coefficient may be omitted or joined to x with `*` or nothing, as in
+/// `x^2 - 3x + 2*x`. Coefficients are parsed as field elements, so `0x` followed
+/// by a hex digit is read as a hexadecimal coefficient rather than 0 times x.
+/// Terms with an exponent above [`MAX_DENSE_DEGREE`] are rejected.
+impl<const M: u32> FromStr for Polynomial<M> {
+	type Err = ParsePolynomialError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut coeffs = Vec::new();
+		// Split into signed terms at each + or -
+		let mut rest = s.trim();
+		let mut negative = false;
+		if let Some(r) = rest.strip_prefix('-') {
+			(negative, rest) = (true, r);
+		}
+		loop {
+			let end = rest.find(['+', '-']).unwrap_or(rest.len());
+			let term = rest[..end].trim();
+			let error = || ParsePolynomialError { term: term.to_string() };
+			let (c, e) = parse_term::<M>(term).ok_or_else(error)?;
+			if coeffs.len() <= e {
+				// Every coefficient up to the exponent is stored, so a huge exponent must
+				// fail to parse rather than exhaust memory
+				if e > MAX_DENSE_DEGREE {
+					return Err(error());
+				}
+				let len = e.checked_add(1).ok_or_else(error)?;
+				coeffs.try_reserve_exact(len - coeffs.len()).map_err(|_| error())?;
+				coeffs.resize(len, Gfe::zero());
+			}
+			coeffs[e] = coeffs[e] + if negative { c.negation() } else { c };
+
+			if end == rest.len() {
+				break;
+			}
+			negative = rest[end..].starts_with('-');
+			rest = &rest[end + 1..];
+		}
+
+		Ok(Polynomial::new(coeffs))
+	}
+}
+
+/// Parse a single term such as `5`, `5 x^2`, `5*x`, `x^3` or `x` into its
+/// coefficient and exponent
+fn parse_term<const M: u32>(term: &str) -> Option<(Gfe<M>, usize)> {
+	// The coefficient is the leading run of digits, including a hex prefix
+	let hex = term.len() > 2 && (term.starts_with("0x") || term.starts_with("0X")) && term.as_bytes()[2].is_ascii_hexdigit();
+	let coeff_len = if hex {
+		2 + term[2..].find(|c: char| !c.is_ascii_hexdigit()).unwrap_or(term.len() - 2)
+	} else {
+		term.find(|c: char| !c.is_ascii_digit()).unwrap_or(term.len())
+	};
+	let (coeff, power) = term.split_at(coeff_len);
+	let power = power.trim();
+
+	if power.is_empty() {
+		return coeff.parse().ok().map(|c| (c, 0));
+	}
+	let c = if coeff.is_empty() { Gfe::one() } else { coeff.parse().ok()? };
+	let power = power.strip_prefix('*').unwrap_or(power).trim_start();
+	let e = match power.strip_prefix('x')?.trim_start() {
+		"" => 1,
+		e => e.strip_prefix('^')?.trim().parse().ok()?,
+	};
+	Some((c, e))
+}
+
+type PolyMatrix<const M: u32> = [[Polynomial<M>; 2]; 2];
+
+fn identity_matrix<const M: u32>() -> PolyMatrix<M> {
+	let (zero, one) = (Polynomial::zero(), Polynomial::one());
+	[[one.clone(), zero.clone()], [zero, one]]
+}
+
+/// The matrix that maps (a, b) to (b, a - q * b)
+fn quotient_matrix<const M: u32>(q: &Polynomial<M>) -> PolyMatrix<M> {
+	let (zero, one) = (Polynomial::zero(), Polynomial::one());
+	[[zero, one.clone()], [one, q.negation()]]
+}
+
+fn mul_matrix<const M: u32>(x: &PolyMatrix<M>, y: &PolyMatrix<M>) -> PolyMatrix<M> {
+	let entry = |i: usize, j: usize| &(&x[i][0] * &y[0][j]) + &(&x[i][1] * &y[1][j]);
+	[[entry(0, 0), entry(0, 1)], [entry(1, 0), entry(1, 1)]]
+}
+
+fn apply_matrix<const M: u32>(x: &PolyMatrix<M>, a: &Polynomial<M>, b: &Polynomial<M>) -> [Polynomial<M>; 2] {
+	[&(&x[0][0] * a) + &(&x[0][1] * b), &(&x[1][0] * a) + &(&x[1][1] * b)]
+}
+
+/// The degree from which [`Polynomial::half_gcd`] and
+/// [`Polynomial::extended_gcd`] recurse rather than running the Euclidean
+/// algorithm. In `benches/gcd.rs` over GF(2^31-1) the two are level at degree
+/// 128 and the half-GCD is ahead from 256 on.
+pub const HALF_GCD_CROSSOVER: usize = 256;
+
+/// Run the Euclidean algorithm on (a, b) until the second remainder has degree
+/// below m, returning the matrix that maps (a, b) to the last two remainders.
+/// With m = 0 this runs until the second remainder is zero.
+fn euclid_matrix<const M: u32>(a: &Polynomial<M>, b: &Polynomial<M>, m: usize) -> PolyMatrix<M> {
+	let [[mut s0, mut t0], [mut s1, mut t1]] = identity_matrix();
+	let (mut r0, mut r1) = (a.clone(), b.clone());
+	while r1.degree() >= Some(m) {
+		let (q, r) = r0.div_rem(&r1);
+		let s = &s0 - &(&q * &s1);
+		let t = &t0 - &(&q * &t1);
+		(r0, r1, s0, s1, t0, t1) = (r1, r, s1, s, t1, t);
+	}
+	[[s0, t0], [s1, t1]]
+}
+
+/// Compute the matrix R such that R * (a, b) = (g, 0) where g is a (not
+/// necessarily monic) greatest common divisor of a and b, using the half-GCD to
+/// skip through the remainder sequence.
+fn gcd_matrix<const M: u32>(a: &Polynomial<M>, b: &Polynomial<M>) -> PolyMatrix<M> {
+	if b.is_zero() {
+		return identity_matrix();
+	}
+	if a.degree().max(b.degree()) < Some(HALF_GCD_CROSSOVER) {
+		return euclid_matrix(a, b, 0);
+	}
+	if a.is_zero() || a.degree() <= b.degree() {
+		// Take a step of the Euclidean algorithm so that the first argument has
+		// the strictly larger degree
+		let (q, r) = a.div_rem(b);
+		return mul_matrix(&gcd_matrix(b, &r), &quotient_matrix(&q));
+	}
+
+	let r = Polynomial::half_gcd(a, b);
+	let [c, d] = apply_matrix(&r, a, b);
+	if d.is_zero() {
+		return r;
+	}
+	let (q, e) = c.div_rem(&d);
+	let r = mul_matrix(&quotient_matrix(&q), &r);
+	mul_matrix(&gcd_matrix(&d, &e), &r)
+}
+
 impl<const M: u32> Display for Polynomial<M> {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.is_zero() {
+			return write!(f, "0");
+		}
 		for i in 0..self.coeffs.len() {
 			write!(f, "{}", self.coeffs[i])?;
 			if i > 0 {
@@ -207,6 +880,22 @@ impl<const M: u32> Display for Polynomial<M> {
 	}
 }
 
+#[test]
+fn berlekamp_massey_test() {
+	// Fibonacci numbers satisfy s_i - s_(i-1) - s_(i-2) = 0
+	let mut fib = vec![Gfe::<19>::one(), Gfe::one()];
+	for i in 2..10 {
+		fib.push(fib[i - 1] + fib[i - 2]);
+	}
+	assert_eq!(Polynomial::berlekamp_massey(&fib), (gfe_poly(&[1, -1, -1]), 2));
+
+	// 0, 0, 1 needs a recurrence of length 3 that only involves s_(i-3)
+	let (c, len) = Polynomial::berlekamp_massey(&gfe_vec::<19>(&[0, 0, 1, 0, 0, 0]));
+	assert_eq!(len, 3);
+	assert!(c.degree() < Some(3));
+	assert_eq!(Polynomial::berlekamp_massey(&gfe_vec::<19>(&[0, 0, 0])), (Polynomial::one(), 0));
+}
+
 #[test]
 fn add_test() {
 	let a = gfe_poly(&[1, 3, 3]);
@@ -229,7 +918,7 @@ fn multiply_test() {
 fn divide_test() {
 	let c = Polynomial::new(vec![Gfe::from(-6), Gfe::from(-5), Gfe::from(5), Gfe::from(5), Gfe::from(1)]);
 	let b = Polynomial::from_roots(&[Gfe::from(-2), Gfe::from(-1)]);
-	let y = c.divide(&b);
+	let y = c.divide(&b).unwrap();
 	let a = Polynomial::from_roots(&[Gfe::from(1), Gfe::from(-3)]);
 	assert_eq!(y.0.coeffs, a.coeffs);
 	assert_eq!(y.1.coeffs, Polynomial::constant(Gfe::<19>::zero()).coeffs);
@@ -239,11 +928,290 @@ fn divide_test() {
 fn divide_test_non_one_leading_coeff() {
 	let a = gfe_poly_roots(&[1, 3, 5]);
 	let b = gfe_poly_roots(&[3, 5]);
-	assert_eq!(a.divide(&b), (gfe_poly_roots(&[1]), Polynomial::<19>::zero()));
+	assert_eq!(a.divide(&b).unwrap(), (gfe_poly_roots(&[1]), Polynomial::<19>::zero()));
 
 	let a = gfe_poly_roots(&[1, 5, 3]) * gfe_poly_const(4);
 	let b = gfe_poly_roots(&[1, 5]) * gfe_poly_const(2);
-	assert_eq!(a.divide(&b), (gfe_poly_roots(&[3]) * gfe_poly_const(2), Polynomial::<19>::zero()));
+	assert_eq!(
+		a.divide(&b).unwrap(),
+		(gfe_poly_roots(&[3]) * gfe_poly_const(2), Polynomial::<19>::zero())
+	);
+}
+
+#[test]
+fn divide_by_constant_test() {
+	// A constant divisor has the same degree as its one term, which once kept the
+	// division loop running forever
+	let a = gfe_poly::<19>(&[3, 1, 4]);
+	assert_eq!(a.divide(&gfe_poly_const(2)).unwrap(), (gfe_poly(&[11, 10, 2]), Polynomial::zero()));
+	assert_eq!(gfe_poly_const::<19>(6).divide(&gfe_poly_const(3)).unwrap(), (gfe_poly_const(2), Polynomial::zero()));
+}
+
+#[test]
+fn zero_polynomial_test() {
+	let zero = Polynomial::<19>::zero();
+	assert_eq!(zero.degree(), None);
+	assert!(zero.coeffs().is_empty());
+	assert_eq!(gfe_poly_const::<19>(0), zero);
+	assert_eq!(gfe_poly::<19>(&[0, 0, 0]), zero);
+	assert_eq!(gfe_poly_const::<19>(5).degree(), Some(0));
+	assert!(zero.degree() < gfe_poly_const::<19>(5).degree());
+	assert_eq!(format!("{zero}"), "0");
+
+	// Operators never leave trailing zeros behind
+	let a = gfe_poly::<19>(&[1, 2, 3]);
+	let b = gfe_poly::<19>(&[4, 5, 16]);
+	assert_eq!((&a + &b).coeffs(), gfe_vec::<19>(&[5, 7]).as_slice());
+	assert_eq!(&a - &a, zero);
+	assert_eq!(zero.negation(), zero);
+	assert_eq!(&a * &zero, zero);
+	assert_eq!(gfe_poly::<19>(&[0, 0, 0, 1]).div_x_pow(5), zero);
+
+	use std::collections::HashSet;
+	let set = [&a + &b, gfe_poly(&[5, 7, 0]), zero.clone(), &a - &a]
+		.into_iter()
+		.collect::<HashSet<_>>();
+	assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn divide_by_zero_test() {
+	let a = gfe_poly::<19>(&[1, 2, 3]);
+	assert_eq!(a.divide(&Polynomial::zero()), Err(DivisionByZero));
+	assert_eq!(Polynomial::zero().divide(&a), Ok((Polynomial::zero(), Polynomial::zero())));
+	assert_eq!(a.divide(&gfe_poly_const(2)).unwrap(), (gfe_poly(&[10, 1, 11]), Polynomial::zero()));
+}
+
+#[test]
+fn gcd_test() {
+	let a = gfe_poly_roots::<19>(&[1, 2, 3, 3]);
+	let b = gfe_poly_roots(&[3, 4, 2]) * gfe_poly_const(7);
+	assert_eq!(a.gcd(&b), gfe_poly_roots(&[2, 3]));
+	assert_eq!(a.gcd(&Polynomial::zero()), a);
+	assert_eq!(gfe_poly_roots::<19>(&[1]).gcd(&gfe_poly_roots(&[2])), gfe_poly_const(1));
+	assert_eq!(Polynomial::<19>::zero().gcd(&Polynomial::zero()), Polynomial::zero());
+}
+
+#[test]
+fn extended_gcd_test() {
+	use crate::field::Gfe2_31;
+	for trial in 0..20 {
+		let len = if trial < 2 { HALF_GCD_CROSSOVER + 20 } else { 20 };
+		let common = rand_poly::<0x7fffffff>(3);
+		let a = &rand_poly(len) * &common;
+		let b = &rand_poly(len - 5) * &common;
+		let (g, s, t) = a.extended_gcd(&b);
+		assert_eq!(g, a.gcd(&b));
+		assert_eq!(g.leading_coeff(), Gfe2_31::one());
+		assert_eq!(&(&s * &a) + &(&t * &b), g);
+		assert!(a.divide(&g).unwrap().1.is_zero() && b.divide(&g).unwrap().1.is_zero());
+	}
+}
+
+#[test]
+fn inverse_mod_test() {
+	let m = gfe_poly::<19>(&[3, 0, 1, 5, 1]);
+	let a = gfe_poly(&[2, 7, 1]);
+	let inv = a.inverse_mod(&m).unwrap();
+	assert_eq!((&a * &inv).divide(&m).unwrap().1, gfe_poly_const(1));
+	assert!(inv.degree() < m.degree());
+
+	let m = gfe_poly_roots::<19>(&[1, 2, 3]);
+	assert_eq!(gfe_poly_roots(&[2, 5]).inverse_mod(&m), None);
+}
+
+#[test]
+fn half_gcd_test() {
+	// Mostly below the crossover, with a few large enough to recurse
+	for trial in 0..20 {
+		let len = if trial < 3 { 2 * HALF_GCD_CROSSOVER + 40 } else { 40 };
+		let a = rand_poly::<0x7fffffff>(len);
+		let b = rand_poly(rand::random::<usize>() % len);
+		let m = a.degree().unwrap().div_ceil(2);
+		let r = Polynomial::half_gcd(&a, &b);
+		let [c, d] = apply_matrix(&r, &a, &b);
+		assert!(c.degree() >= Some(m));
+		assert!(d.degree() < Some(m));
+
+		// (c, d) must be consecutive remainders of the Euclidean algorithm
+		let (mut x, mut y) = (a.clone(), b.clone());
+		while !(x == c && y == d) {
+			assert!(!y.is_zero());
+			let r = x.divide(&y).unwrap().1;
+			x = y;
+			y = r;
+		}
+	}
+}
+
+#[test]
+fn roots_test() {
+	let f = gfe_poly_roots::<19>(&[3, 1, 7, 7, 0, 18]) * gfe_poly(&[17, 0, 1]) * gfe_poly_const(5);
+	assert_eq!(f.roots(), gfe_vec(&[0, 1, 3, 7, 18]));
+	assert_eq!(f.roots_in((0..19).map(Gfe::new)), gfe_vec(&[0, 1, 3, 7, 18]));
+	assert_eq!(f.roots_in((2..10).map(Gfe::new)), gfe_vec(&[3, 7]));
+	assert_eq!(gfe_poly::<19>(&[17, 0, 1]).roots(), vec![]);
+	assert_eq!(gfe_poly_const::<19>(4).roots(), vec![]);
+
+	use crate::field::Gfe2_31;
+	let roots = [5, 123456789, 0x7ffffffe, 77].map(Gfe2_31::new);
+	let f = Polynomial::from_roots(&roots) * rand_poly(6);
+	let found = f.roots();
+	assert!(roots.iter().all(|r| found.contains(r)));
+	assert!(found.iter().all(|&r| f.eval(r) == Gfe2_31::zero()));
+}
+
+#[test]
+fn factor_test() {
+	let irreducible = gfe_poly::<19>(&[17, 0, 1]);
+	let cubic = gfe_poly::<19>(&[1, 0, 1, 1]);
+	assert!(cubic.roots().is_empty());
+	let f = gfe_poly_roots(&[3, 3, 3, 5]) * irreducible.clone() * irreducible.clone() * cubic.clone() * gfe_poly_const(6);
+	assert_eq!(
+		f.factor(),
+		vec![(gfe_poly_roots(&[5]), 1), (gfe_poly_roots(&[3]), 3), (irreducible, 2), (cubic, 1)]
+	);
+
+	// x^19 - x^2 has a factor whose derivative vanishes
+	let f = gfe_poly::<19>(&[0, 0, 18]) + Polynomial::single(Gfe::one(), 19);
+	let product = f
+		.factor()
+		.iter()
+		.fold(Polynomial::one(), |acc, (g, e)| (0..*e).fold(acc, |acc, _| &acc * g));
+	assert_eq!(product, f);
+	assert!(f.factor().iter().all(|(g, _)| g.factor() == vec![(g.clone(), 1)]));
+
+	use crate::field::Gfe2_31;
+	let a = rand_poly::<0x7fffffff>(3);
+	let f = &(&a * &a) * &Polynomial::from_roots(&[Gfe2_31::new(9)]);
+	let product = f
+		.factor()
+		.iter()
+		.fold(Polynomial::one(), |acc, (g, e)| (0..*e).fold(acc, |acc, _| &acc * g));
+	assert_eq!(product, f);
+}
+
+#[test]
+fn derivative_test() {
+	assert_eq!(gfe_poly::<19>(&[4, 3, 5, 1]).derivative(), gfe_poly(&[3, 10, 3]));
+	assert_eq!(gfe_poly_const::<19>(7).derivative(), Polynomial::zero());
+	// x^19 has a zero derivative in GF(19)
+	assert_eq!(Polynomial::<19>::single(Gfe::one(), 19).derivative(), Polynomial::zero());
+
+	// A repeated root is a root of the derivative as well
+	let f = gfe_poly_roots::<0x7fffffff>(&[5, 5, 1000]);
+	assert_eq!(f.derivative().eval(Gfe::new(5)), Gfe::zero());
+	assert_eq!(f.gcd(&f.derivative()), gfe_poly_roots(&[5]));
+}
+
+#[test]
+fn compose_test() {
+	let p = gfe_poly::<19>(&[1, 2, 3]);
+	let q = gfe_poly(&[4, 0, 1]);
+	// 1 + 2(x^2 + 4) + 3(x^2 + 4)^2 = 57 + 26x^2 + 3x^4
+	assert_eq!(p.compose(&q), gfe_poly(&[57, 0, 26, 0, 3]));
+	assert_eq!(p.compose(&gfe_poly_const(2)), gfe_poly_const(17));
+
+	let p = rand_poly::<0x7fffffff>(7);
+	let q = rand_poly(4);
+	let x = Gfe::new(123456);
+	assert_eq!(p.compose(&q).eval(x), p.eval(q.eval(x)));
+	assert_eq!(p.compose(&q).degree(), Some(28));
+}
+
+#[test]
+fn pow_mod_test() {
+	let m = gfe_poly::<19>(&[3, 0, 1, 5, 1]);
+	let a = gfe_poly(&[2, 7, 1]);
+	let mut expected = gfe_poly_const(1);
+	for e in 0..30 {
+		assert_eq!(a.pow_mod(e, &m), expected);
+		expected = (&expected * &a).divide(&m).unwrap().1;
+	}
+
+	// Fermat: x^p = x in GF(p)[x]/(x - c) for any c
+	let m = gfe_poly_roots::<0x7fffffff>(&[31337]);
+	let x = Polynomial::single(Gfe::one(), 1);
+	assert_eq!(x.pow_mod(0x7fffffff, &m), gfe_poly_const(31337));
+}
+
+#[test]
+fn shift_test() {
+	// (x + 2)^2 = x^2 + 4x + 4
+	assert_eq!(gfe_poly::<19>(&[0, 0, 1]).shift(Gfe::new(2)), gfe_poly(&[4, 4, 1]));
+	assert_eq!(gfe_poly_roots::<19>(&[3, 5]).shift(Gfe::new(3)), gfe_poly_roots(&[0, 2]));
+
+	let p = rand_poly::<0x7fffffff>(12);
+	let c = Gfe::new(987654321);
+	let shifted = p.shift(c);
+	assert_eq!(shifted, p.compose(&gfe_poly(&[*c as i64, 1])));
+	assert_eq!(shifted.shift(c.negation()), p);
+}
+
+#[test]
+fn eval_test() {
+	let p = gfe_poly::<19>(&[4, 3, 5, 1]);
+	assert_eq!(p.eval(Gfe::new(2)), Gfe::zero());
+	assert_eq!(Polynomial::<19>::zero().eval(Gfe::new(5)), Gfe::zero());
+	assert_eq!(p.eval_many(gfe_vec(&[0, 1, 2])), gfe_vec(&[4, 13, 38]));
+
+	let p = rand_poly::<0x7fffffff>(9);
+	let expected = (0..40).map(|x| p.eval(Gfe::new(0x7fffffff - 20 + x))).collect::<Vec<_>>();
+	assert_eq!(p.eval_range(Gfe::new(0x7fffffff - 20), 40), expected);
+	assert_eq!(p.eval_range(Gfe::new(3), 4), p.eval_many((3..7).map(Gfe::new)));
+	assert_eq!(gfe_poly_const::<19>(6).eval_range(Gfe::new(18), 3), gfe_vec(&[6, 6, 6]));
+	assert_eq!(p.eval_range(Gfe::zero(), 0), vec![]);
+
+	// Evenly spaced points go through the difference table, others through blocks
+	// of Horner's rule
+	let spaced = (0..30).map(|t| Gfe::new(5 + 7 * t)).collect::<Vec<_>>();
+	let scattered = (0..30).map(|t| Gfe::new(t * t * 1000 + 1)).collect::<Vec<_>>();
+	for xs in [spaced, scattered] {
+		assert_eq!(p.eval_many(xs.clone()), xs.iter().map(|&x| p.eval(x)).collect::<Vec<_>>());
+	}
+	assert_eq!(Polynomial::<19>::zero().eval_many(gfe_vec(&[1, 2, 3])), gfe_vec(&[0, 0, 0]));
+}
+
+#[test]
+fn parse_test() {
+	assert_eq!("3 + 2 x + 5 x^2".parse(), Ok(gfe_poly::<19>(&[3, 2, 5])));
+	assert_eq!("x^2 + 3x".parse(), Ok(gfe_poly::<19>(&[0, 3, 1])));
+	assert_eq!("-x + 4 - 2*x^3".parse(), Ok(gfe_poly::<19>(&[4, -1, 0, -2])));
+	assert_eq!("x+x+x".parse(), Ok(gfe_poly::<19>(&[0, 3])));
+	assert_eq!("0x10 x^ 2 + 0x + 25".parse(), Ok(gfe_poly::<19>(&[6, 0, 16])));
+	assert_eq!("0".parse(), Ok(Polynomial::<19>::zero()));
+	assert_eq!("x - x".parse(), Ok(Polynomial::<19>::zero()));
+
+	assert_eq!(
+		"3 + + x".parse::<Polynomial<19>>(),
+		Err(ParsePolynomialError { term: "".to_string() })
+	);
+	assert_eq!("".parse::<Polynomial<19>>(), Err(ParsePolynomialError { term: "".to_string() }));
+	assert_eq!(
+		"2 y".parse::<Polynomial<19>>(),
+		Err(ParsePolynomialError { term: "2 y".to_string() })
+	);
+	assert_eq!(
+		"x^-1".parse::<Polynomial<19>>(),
+		Err(ParsePolynomialError { term: "x^".to_string() })
+	);
+	assert_eq!(
+		"3 x 2".parse::<Polynomial<19>>(),
+		Err(ParsePolynomialError { term: "3 x 2".to_string() })
+	);
+
+	// Exponents that would overflow or need an enormous allocation
+	for term in ["x^18446744073709551615", "x^1000000000000", "2 x^16777217"] {
+		assert_eq!(term.parse::<Polynomial<19>>(), Err(ParsePolynomialError { term: term.to_string() }));
+	}
+	assert_eq!("x^16777216".parse::<Polynomial<19>>().unwrap().degree(), Some(MAX_DENSE_DEGREE));
+
+	// Round trip through Display
+	for p in [gfe_poly::<19>(&[3, 0, 18, 1]), Polynomial::zero(), gfe_poly_const(7)] {
+		assert_eq!(p.to_string().parse(), Ok(p));
+	}
+	let p = rand_poly::<0x7fffffff>(10);
+	assert_eq!(p.to_string().parse(), Ok(p));
 }
 
 #[cfg(test)]
@@ -0,0 +1,67 @@
+use crate::{check_multipliers, decode_erasures_with, field::Gfe, Encoding};
+
+/// Correct a message using a reliability score for each received value, where a
+/// higher score means the value is more likely to be correct. The corrected
+/// encoded message is written back to r. Errors in unreliable positions cost
+/// less than errors in reliable ones, so more than k errors can be corrected when
+/// most of them are in the least reliable positions.
+#[allow(clippy::result_unit_err)]
+pub fn decode_soft<const M: u32>(k: usize, r: &mut [Gfe<M>], reliability: &[f64]) -> Result<(), ()> {
+	decode_soft_with(k, r, &vec![Gfe::one(); r.len()], Encoding::Systematic, reliability).map(|_| ())
+}
+
+/// Correct a message encoded with [`crate::encode_with`] using the column
+/// multipliers v and the given encoding, given a reliability score for each
+/// received value. The corrected encoded message is written back to r and the
+/// original message is returned in the representation matching the encoding.
+///
+/// This is generalized minimum distance decoding: the 0, 2, 4, ..., 2k least
+/// reliable positions are erased in turn and decoded with
+/// [`decode_erasures_with`]. Of the codewords found, the one returned is the one
+/// whose disagreements with r have the smallest total reliability.
+#[allow(clippy::result_unit_err)]
+pub fn decode_soft_with<const M: u32>(
+	k: usize,
+	r: &mut [Gfe<M>],
+	v: &[Gfe<M>],
+	encoding: Encoding,
+	reliability: &[f64],
+) -> Result<Vec<Gfe<M>>, ()> {
+	if reliability.len() != r.len() {
+		panic!("Expected {} reliabilities, got {}", r.len(), reliability.len())
+	}
+	check_multipliers(r.len(), v);
+
+	// Positions from least to most reliable
+	let mut order = (0..r.len()).collect::<Vec<_>>();
+	order.sort_by(|&a, &b| reliability[a].total_cmp(&reliability[b]));
+
+	let mut best: Option<(f64, Vec<Gfe<M>>, Vec<Gfe<M>>)> = None;
+	for erased in (0..=2 * k).step_by(2) {
+		let mut candidate = r.to_vec();
+		let Ok(message) = decode_erasures_with(k, &mut candidate, v, encoding, &order[..erased]) else {
+			continue;
+		};
+		let cost = soft_distance(&candidate, r, reliability);
+		if best.as_ref().map_or(true, |(best_cost, _, _)| cost < *best_cost) {
+			best = Some((cost, candidate, message));
+		}
+	}
+
+	let (_, codeword, message) = best.ok_or(())?;
+	r.copy_from_slice(&codeword);
+	Ok(message)
+}
+
+/// The total reliability of the positions where a and b differ
+fn soft_distance<const M: u32>(a: &[Gfe<M>], b: &[Gfe<M>], reliability: &[f64]) -> f64 {
+	a.iter().zip(b).zip(reliability).filter(|((x, y), _)| x != y).map(|(_, &w)| w).sum()
+}
+
+#[test]
+fn soft_distance_test() {
+	let a = [1, 2, 3, 4].map(Gfe::<19>::new);
+	let b = [1, 5, 3, 0].map(Gfe::<19>::new);
+	assert_eq!(soft_distance(&a, &b, &[8.0, 0.25, 4.0, 0.5]), 0.75);
+	assert_eq!(soft_distance(&a, &a, &[1.0; 4]), 0.0);
+}
@@ -0,0 +1,267 @@
+use std::ops::Mul;
+
+use crate::{
+	field::{Gfe, Xorshift},
+	matrix::Matrix,
+	polynomial::Polynomial,
+};
+#[cfg(test)]
+use crate::test::gfe_vec;
+
+/// A matrix over GF(M) in compressed sparse row form, which stores only the
+/// nonzero elements, for matrices such as parity checks where almost every
+/// element is zero.
+///
+/// The nonzero elements of row i are at positions row_starts[i]..row_starts[i+1]
+/// of cols and values, sorted by column.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SparseMatrix<const M: u32> {
+	m: usize,
+	n: usize,
+	row_starts: Vec<usize>,
+	cols: Vec<usize>,
+	values: Vec<Gfe<M>>,
+}
+
+impl<const M: u32> SparseMatrix<M> {
+	/// Construct an m by n matrix from (row, column, value) triplets in any order.
+	/// Triplets at the same position are added together.
+	pub fn new(m: usize, n: usize, triplets: Vec<(usize, usize, Gfe<M>)>) -> Self {
+		if let Some(&(i, j, _)) = triplets.iter().find(|&&(i, j, _)| i >= m || j >= n) {
+			panic!("Position ({i}, {j}) out of range for a {m}x{n} matrix")
+		}
+		let mut triplets = triplets;
+		triplets.sort_by_key(|&(i, j, _)| (i, j));
+
+		let mut row_starts = vec![0; m + 1];
+		let mut cols: Vec<usize> = Vec::with_capacity(triplets.len());
+		let mut values: Vec<Gfe<M>> = Vec::with_capacity(triplets.len());
+		let mut last = None;
+		for (i, j, c) in triplets {
+			if last == Some((i, j)) {
+				let value = values.last_mut().unwrap();
+				*value = *value + c;
+			} else {
+				cols.push(j);
+				values.push(c);
+				row_starts[i + 1] += 1;
+				last = Some((i, j));
+			}
+		}
+		for i in 0..m {
+			row_starts[i + 1] += row_starts[i];
+		}
+
+		// Drop the elements that were zero or added up to zero
+		let mut matrix = Self {
+			m,
+			n,
+			row_starts,
+			cols,
+			values,
+		};
+		matrix.remove_zeros();
+		matrix
+	}
+
+	fn remove_zeros(&mut self) {
+		let mut kept = 0;
+		let mut start = 0;
+		for i in 0..self.m {
+			let end = self.row_starts[i + 1];
+			for k in start..end {
+				if self.values[k] != Gfe::zero() {
+					self.cols[kept] = self.cols[k];
+					self.values[kept] = self.values[k];
+					kept += 1;
+				}
+			}
+			start = end;
+			self.row_starts[i + 1] = kept;
+		}
+		self.cols.truncate(kept);
+		self.values.truncate(kept);
+	}
+
+	/// The number of rows
+	pub fn m(&self) -> usize {
+		self.m
+	}
+
+	/// The number of columns
+	pub fn n(&self) -> usize {
+		self.n
+	}
+
+	/// The number of nonzero elements
+	pub fn nnz(&self) -> usize {
+		self.values.len()
+	}
+
+	/// The nonzero elements of row i as (column, value) pairs in increasing order
+	/// of column
+	pub fn row(&self, i: usize) -> impl Iterator<Item = (usize, Gfe<M>)> + '_ {
+		let range = self.row_starts[i]..self.row_starts[i + 1];
+		self.cols[range.clone()].iter().copied().zip(self.values[range].iter().copied())
+	}
+
+	/// Solve A x = b for a square nonsingular matrix A with Wiedemann's algorithm,
+	/// which only multiplies A by vectors, so it takes O(n) products costing
+	/// O(nnz) each and never fills in the zeros of A.
+	///
+	/// The minimal polynomial f of A acting on b is found by running
+	/// Berlekamp-Massey on the scalar sequence u^T A^i b for a pseudo-random
+	/// projection u. Then f(A) b = 0, and when f(0) != 0 the solution is a
+	/// combination of the vectors A^i b.
+	///
+	/// For a uniformly random u, the sequence has a shorter minimal polynomial
+	/// than f, which makes the attempt fail, with probability at most n / M. The
+	/// projections come from a fixed pseudo-random sequence, so solving is
+	/// deterministic, and up to 8 are tried. Over GF(2^31 - 1) a nonsingular
+	/// system of size 1000 is then reported unsolvable with probability around
+	/// (1000 / 2^31)^8, while over a small field, where n / M is not small, it can
+	/// happen for nonsingular systems. Returns None if no solution was found.
+	pub fn solve_wiedemann(&self, b: &[Gfe<M>]) -> Option<Vec<Gfe<M>>> {
+		const ATTEMPTS: u64 = 8;
+
+		if self.m != self.n {
+			panic!("Expected a square matrix, got {}x{}", self.m, self.n)
+		}
+		if b.len() != self.n {
+			panic!("Expected a right hand side of length {}, got {}", self.n, b.len())
+		}
+		let n = self.n;
+		if b.iter().all(|&y| y == Gfe::zero()) {
+			return Some(vec![Gfe::zero(); n]);
+		}
+
+		for attempt in 0..ATTEMPTS {
+			let mut rng = Xorshift::new(attempt);
+			let u = (0..n).map(|_| rng.next_gfe()).collect::<Vec<_>>();
+
+			// s_i = u^T A^i b for i < 2n determines a recurrence of length up to n
+			let mut sequence = Vec::with_capacity(2 * n);
+			let mut v = b.to_vec();
+			for _ in 0..(2 * n) {
+				sequence.push(Gfe::dot(&u, &v));
+				v = self * &v[..];
+			}
+			let (c, len) = Polynomial::berlekamp_massey(&sequence);
+
+			// f(x) = sum_j c_j x^(len-j) so f(A) b = 0 gives
+			// b = A * -(1 / c_len) sum_{j<len} c_j A^(len-1-j) b
+			let c_len = c.coeff(len);
+			if len == 0 || c_len == Gfe::zero() {
+				continue;
+			}
+			let mut x = vec![Gfe::zero(); n];
+			for j in 0..len {
+				x = self * &x[..];
+				for (xi, &bi) in x.iter_mut().zip(b) {
+					*xi = xi.mul_add(c.coeff(j), bi);
+				}
+			}
+			let scale = c_len.inverse().negation();
+			let x = x.into_iter().map(|xi| xi * scale).collect::<Vec<_>>();
+
+			if self * &x[..] == b {
+				return Some(x);
+			}
+		}
+		None
+	}
+}
+
+impl<const M: u32> From<&Matrix<M>> for SparseMatrix<M> {
+	fn from(mat: &Matrix<M>) -> Self {
+		let mut triplets = Vec::new();
+		for (i, row) in mat.rows().enumerate() {
+			triplets.extend(row.iter().enumerate().filter(|(_, &c)| c != Gfe::zero()).map(|(j, &c)| (i, j, c)));
+		}
+		Self::new(mat.m(), mat.n(), triplets)
+	}
+}
+
+/// Expand a sparse matrix into every element
+impl<const M: u32> From<&SparseMatrix<M>> for Matrix<M> {
+	fn from(sparse: &SparseMatrix<M>) -> Self {
+		let mut mat = Matrix::zeros(sparse.m, sparse.n);
+		for i in 0..sparse.m {
+			for (j, c) in sparse.row(i) {
+				mat[(i, j)] = c;
+			}
+		}
+		mat
+	}
+}
+
+/// Multiply a sparse matrix by a column vector
+impl<const M: u32> Mul<&'_ [Gfe<M>]> for &'_ SparseMatrix<M> {
+	type Output = Vec<Gfe<M>>;
+
+	fn mul(self, rhs: &[Gfe<M>]) -> Self::Output {
+		if self.n != rhs.len() {
+			panic!("Cannot multiply a {}x{} matrix by a vector of length {}", self.m, self.n, rhs.len())
+		}
+		(0..self.m)
+			.map(|i| self.row(i).fold(Gfe::zero(), |acc, (j, c)| acc.mul_add(c, rhs[j])))
+			.collect()
+	}
+}
+
+#[test]
+fn csr_test() {
+	use crate::field::Gfe19;
+	let a = SparseMatrix::new(
+		3,
+		4,
+		vec![
+			(2, 1, Gfe19::new(5)),
+			(0, 3, Gfe::new(2)),
+			(0, 0, Gfe::new(1)),
+			(2, 1, Gfe::new(3)),
+			(1, 2, Gfe::new(7)),
+			(1, 2, Gfe::new(12)),
+			(2, 0, Gfe::new(0)),
+		],
+	);
+	// (1, 2) added up to zero and (2, 0) was zero
+	assert_eq!(a.nnz(), 3);
+	assert_eq!(a.row(0).collect::<Vec<_>>(), vec![(0, Gfe::new(1)), (3, Gfe::new(2))]);
+	assert_eq!(a.row(1).count(), 0);
+	assert_eq!(a.row(2).collect::<Vec<_>>(), vec![(1, Gfe::new(8))]);
+
+	let dense = Matrix::from(&a);
+	assert_eq!(SparseMatrix::from(&dense), a);
+	let x = gfe_vec(&[1, 2, 3, 4]);
+	assert_eq!(&a * &x[..], &dense * &x[..]);
+}
+
+#[test]
+fn wiedemann_test() {
+	use crate::field::Gfe2_31;
+	// A sparse nonsingular matrix: the identity plus a cyclic shift scaled by 3,
+	// with a few extra elements
+	let n = 50;
+	let mut triplets = (0..n).map(|i| (i, i, Gfe2_31::one())).collect::<Vec<_>>();
+	triplets.extend((0..n).map(|i| (i, (i + 1) % n, Gfe::new(3))));
+	triplets.extend([(7, 30, Gfe::new(11)), (41, 2, Gfe::new(5))]);
+	let a = SparseMatrix::new(n, n, triplets);
+	let b = (0..n).map(|i| Gfe::new(i as u32 * 7 + 1)).collect::<Vec<_>>();
+	let x = a.solve_wiedemann(&b).unwrap();
+	assert_eq!(&a * &x[..], b);
+
+	// Over a small field too, where unlucky projections are more likely
+	let a = SparseMatrix::<19>::new(
+		3,
+		3,
+		vec![(0, 1, Gfe::new(2)), (1, 2, Gfe::new(3)), (2, 0, Gfe::new(4)), (2, 2, Gfe::new(1))],
+	);
+	let b = gfe_vec(&[1, 0, 5]);
+	assert_eq!(&a * &a.solve_wiedemann(&b).unwrap()[..], b);
+
+	// Singular: the last row is zero
+	let singular = SparseMatrix::<19>::new(2, 2, vec![(0, 0, Gfe::new(1)), (0, 1, Gfe::new(1))]);
+	assert_eq!(singular.solve_wiedemann(&gfe_vec(&[1, 1])), None);
+	assert_eq!(singular.solve_wiedemann(&gfe_vec(&[0, 0])), Some(gfe_vec(&[0, 0])));
+}
@@ -0,0 +1,278 @@
+use std::{
+	fmt::Display,
+	ops::{Add, Mul, Sub},
+};
+
+use crate::{
+	field::Gfe,
+	polynomial::{DivisionByZero, Polynomial, MAX_DENSE_DEGREE},
+};
+
+/// A polynomial over GF(M) stored as its nonzero terms, for polynomials such as
+/// x^M - x whose degree is far too large to store every coefficient.
+///
+/// Terms are (exponent, coefficient) pairs sorted by increasing exponent with no
+/// repeated exponents and no zero coefficients, so the zero polynomial has no
+/// terms and every polynomial has exactly one representation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SparsePolynomial<const M: u32> {
+	terms: Vec<(u64, Gfe<M>)>,
+}
+
+impl<const M: u32> SparsePolynomial<M> {
+	/// Construct a polynomial from (exponent, coefficient) terms in any order.
+	/// Terms with the same exponent are added together.
+	pub fn new(terms: Vec<(u64, Gfe<M>)>) -> Self {
+		let mut terms = terms;
+		terms.sort_by_key(|&(e, _)| e);
+		let mut normalized: Vec<(u64, Gfe<M>)> = Vec::with_capacity(terms.len());
+		for (e, c) in terms {
+			match normalized.last_mut() {
+				Some(last) if last.0 == e => last.1 = last.1 + c,
+				_ => normalized.push((e, c)),
+			}
+		}
+		normalized.retain(|&(_, c)| c != Gfe::zero());
+		Self { terms: normalized }
+	}
+
+	pub fn zero() -> Self {
+		Self { terms: Vec::new() }
+	}
+
+	/// The polynomial c * x^e
+	pub fn monomial(c: Gfe<M>, e: u64) -> Self {
+		Self::new(vec![(e, c)])
+	}
+
+	/// The nonzero terms of this polynomial as (exponent, coefficient) pairs in
+	/// increasing order of exponent
+	pub fn terms(&self) -> &[(u64, Gfe<M>)] {
+		&self.terms
+	}
+
+	/// The degree of this polynomial, or None for the zero polynomial
+	pub fn degree(&self) -> Option<u64> {
+		self.terms.last().map(|&(e, _)| e)
+	}
+
+	pub fn is_zero(&self) -> bool {
+		self.terms.is_empty()
+	}
+
+	pub fn eval(&self, x: Gfe<M>) -> Gfe<M> {
+		self.terms.iter().fold(Gfe::zero(), |y, &(e, c)| y + c * pow(x, e))
+	}
+
+	pub fn negation(&self) -> Self {
+		Self {
+			terms: self.terms.iter().map(|&(e, c)| (e, c.negation())).collect(),
+		}
+	}
+
+	/// The remainder of this polynomial divided by a dense polynomial m.
+	///
+	/// Each x^e is reduced with repeated squaring modulo m, stepping from the
+	/// previous term's power, so the cost depends on the number of terms and
+	/// log(degree) rather than the degree itself.
+	pub fn rem_dense(&self, m: &Polynomial<M>) -> Result<Polynomial<M>, DivisionByZero> {
+		if m.is_zero() {
+			return Err(DivisionByZero);
+		}
+
+		let x = Polynomial::single(Gfe::one(), 1);
+		let mut result = Polynomial::zero();
+		// x^e mod m for the exponent of the previous term
+		let (mut e_prev, mut power) = (0, Polynomial::one().divide(m)?.1);
+		for &(e, c) in &self.terms {
+			power = (&power * &x.pow_mod(e - e_prev, m)).divide(m)?.1;
+			e_prev = e;
+			result = &result + &(&Polynomial::constant(c) * &power);
+		}
+		Ok(result)
+	}
+}
+
+/// x^e without the i32 exponent limit of [`Gfe::power`], using x^(M-1) = 1 for
+/// nonzero x
+fn pow<const M: u32>(x: Gfe<M>, e: u64) -> Gfe<M> {
+	if x == Gfe::zero() {
+		return if e == 0 { Gfe::one() } else { Gfe::zero() };
+	}
+	x.power((e % (M as u64 - 1)) as i32)
+}
+
+impl<const M: u32> From<&Polynomial<M>> for SparsePolynomial<M> {
+	fn from(p: &Polynomial<M>) -> Self {
+		Self::new(p.coeffs().iter().enumerate().map(|(e, &c)| (e as u64, c)).collect())
+	}
+}
+
+/// Error returned when a sparse polynomial has too high a degree to expand into
+/// every coefficient
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DegreeTooLarge {
+	pub degree: u64,
+}
+
+impl Display for DegreeTooLarge {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "degree {} is above the dense limit of {}", self.degree, MAX_DENSE_DEGREE)
+	}
+}
+
+impl std::error::Error for DegreeTooLarge {}
+
+/// Expand a sparse polynomial into every coefficient up to its degree, which
+/// fails above [`MAX_DENSE_DEGREE`]. Polynomials like x^M - x should be reduced
+/// with [`SparsePolynomial::rem_dense`] instead.
+impl<const M: u32> TryFrom<&SparsePolynomial<M>> for Polynomial<M> {
+	type Error = DegreeTooLarge;
+
+	fn try_from(p: &SparsePolynomial<M>) -> Result<Self, Self::Error> {
+		let len = match p.degree() {
+			Some(d) if d > MAX_DENSE_DEGREE as u64 => return Err(DegreeTooLarge { degree: d }),
+			Some(d) => d as usize + 1,
+			None => 0,
+		};
+		let mut coeffs = vec![Gfe::zero(); len];
+		for &(e, c) in &p.terms {
+			coeffs[e as usize] = c;
+		}
+		Ok(Polynomial::new(coeffs))
+	}
+}
+
+impl<const M: u32> Add for &'_ SparsePolynomial<M> {
+	type Output = SparsePolynomial<M>;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		SparsePolynomial::new(self.terms.iter().chain(rhs.terms.iter()).copied().collect())
+	}
+}
+
+impl<const M: u32> Add for SparsePolynomial<M> {
+	type Output = SparsePolynomial<M>;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		&self + &rhs
+	}
+}
+
+impl<const M: u32> Sub for &'_ SparsePolynomial<M> {
+	type Output = SparsePolynomial<M>;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		let negated = rhs.terms.iter().map(|&(e, c)| (e, c.negation()));
+		SparsePolynomial::new(self.terms.iter().copied().chain(negated).collect())
+	}
+}
+
+impl<const M: u32> Sub for SparsePolynomial<M> {
+	type Output = SparsePolynomial<M>;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		&self - &rhs
+	}
+}
+
+impl<const M: u32> Mul for &'_ SparsePolynomial<M> {
+	type Output = SparsePolynomial<M>;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		let mut terms = Vec::with_capacity(self.terms.len() * rhs.terms.len());
+		for &(e1, c1) in &self.terms {
+			for &(e2, c2) in &rhs.terms {
+				terms.push((e1 + e2, c1 * c2));
+			}
+		}
+		SparsePolynomial::new(terms)
+	}
+}
+
+impl<const M: u32> Mul for SparsePolynomial<M> {
+	type Output = SparsePolynomial<M>;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		&self * &rhs
+	}
+}
+
+impl<const M: u32> Display for SparsePolynomial<M> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.is_zero() {
+			return write!(f, "0");
+		}
+		for (i, &(e, c)) in self.terms.iter().enumerate() {
+			write!(f, "{c}")?;
+			if e > 0 {
+				write!(f, " x")?;
+			}
+			if e > 1 {
+				write!(f, "^{e}")?;
+			}
+			if i + 1 < self.terms.len() {
+				write!(f, " + ")?;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+#[test]
+fn normalize_test() {
+	use crate::field::Gfe19;
+	let p = SparsePolynomial::new(vec![(5, Gfe19::new(3)), (0, Gfe::new(1)), (5, Gfe::new(16)), (2, Gfe::new(0))]);
+	assert_eq!(p.terms(), &[(0, Gfe::new(1))]);
+	assert_eq!(p.degree(), Some(0));
+	assert_eq!(&p - &p, SparsePolynomial::zero());
+	assert_eq!(SparsePolynomial::<19>::zero().degree(), None);
+	assert_eq!(SparsePolynomial::monomial(Gfe19::new(2), 1 << 40).to_string(), "2 x^1099511627776");
+}
+
+#[test]
+fn arithmetic_test() {
+	use crate::field::Gfe2_31;
+	let big = 1u64 << 31;
+	let a = SparsePolynomial::new(vec![(big, Gfe2_31::one()), (1, Gfe::one().negation())]);
+	let b = SparsePolynomial::new(vec![(big, Gfe2_31::new(3)), (0, Gfe::new(7))]);
+	assert_eq!(
+		(&a + &b).terms(),
+		&[(0, Gfe::new(7)), (1, Gfe::one().negation()), (big, Gfe::new(4))]
+	);
+	let product = &a * &b;
+	assert_eq!(product.degree(), Some(2 * big));
+	let x = Gfe2_31::new(123456);
+	assert_eq!(product.eval(x), a.eval(x) * b.eval(x));
+	// x^(2^31) = x^2 for nonzero x in GF(2^31 - 1)
+	assert_eq!(a.eval(x), x * x + x.negation());
+	assert_eq!(a.eval(Gfe::zero()), Gfe::zero());
+}
+
+#[test]
+fn dense_conversion_test() {
+	let dense = "4 + 3 x^2 + 18 x^7".parse::<Polynomial<19>>().unwrap();
+	let sparse = SparsePolynomial::from(&dense);
+	assert_eq!(sparse.terms(), &[(0, Gfe::new(4)), (2, Gfe::new(3)), (7, Gfe::new(18))]);
+	assert_eq!(Polynomial::try_from(&sparse), Ok(dense));
+	assert_eq!(Polynomial::try_from(&SparsePolynomial::<19>::zero()), Ok(Polynomial::zero()));
+	let vanishing = SparsePolynomial::<0x7fffffff>::new(vec![(0x7fffffff, Gfe::one()), (1, Gfe::one().negation())]);
+	assert_eq!(Polynomial::try_from(&vanishing), Err(DegreeTooLarge { degree: 0x7fffffff }));
+}
+
+#[test]
+fn rem_dense_test() {
+	// Compare against reducing the expanded polynomial
+	let p = SparsePolynomial::<19>::new(vec![(50, Gfe::new(3)), (23, Gfe::new(5)), (0, Gfe::new(1)), (7, Gfe::new(2))]);
+	let m = "2 + x + 3 x^3 + x^4".parse::<Polynomial<19>>().unwrap();
+	assert_eq!(p.rem_dense(&m), Ok(Polynomial::try_from(&p).unwrap().divide(&m).unwrap().1));
+	assert_eq!(p.rem_dense(&Polynomial::zero()), Err(DivisionByZero));
+
+	// x^p - x vanishes on every element of GF(p), so it is divisible by any
+	// product of distinct linear factors
+	use crate::field::Gfe2_31;
+	let roots = [3, 77, 0x7ffffffe].map(Gfe2_31::new);
+	let vanishing = SparsePolynomial::new(vec![(0x7fffffff, Gfe::one()), (1, Gfe::one().negation())]);
+	assert!(vanishing.rem_dense(&Polynomial::from_roots(&roots)).unwrap().is_zero());
+}
@@ -1,10 +1,54 @@
-use crate::*;
+use crate::{polynomial::Polynomial, *};
 use rand::Rng;
 
+// Helpers shared by the tests of every module
+
+/// The field most randomized tests run over
+pub(crate) const M: u32 = 0x7fffffff; // 2^31-1
+/// The number of random trials in each randomized test
+pub(crate) const TRIALS: usize = 20;
+
+pub(crate) fn gfe_vec<const M: u32>(ints: &[i64]) -> Vec<Gfe<M>> {
+	ints.iter().map(|&x| Gfe::from(x)).collect()
+}
+
+pub(crate) fn rand_nonzero_gfes<const M: u32>(len: usize) -> Vec<Gfe<M>> {
+	rand::thread_rng()
+		.sample_iter(rand::distributions::Uniform::new(1, M))
+		.take(len)
+		.map(Gfe::<M>::new)
+		.collect()
+}
+
+/// Replace e randomly chosen values of the message with different values
+pub(crate) fn corrupt<const M: u32>(message: &mut [Gfe<M>], e: usize) {
+	for i in rand::seq::index::sample(&mut rand::thread_rng(), message.len(), e) {
+		message[i] = rand_gfe_except(message[i]);
+	}
+}
+
+pub(crate) fn rand_gfe_except<const M: u32>(x: Gfe<M>) -> Gfe<M> {
+	let y = rand::thread_rng().sample(rand::distributions::Uniform::new(0, M - 1));
+	if y < *x {
+		Gfe::new(y)
+	} else {
+		Gfe::new(y + 1)
+	}
+}
+
+/// A random monic polynomial of degree d
+pub(crate) fn rand_poly<const M: u32>(d: usize) -> Polynomial<M> {
+	let mut coeffs = rand::thread_rng()
+		.sample_iter(rand::distributions::Uniform::new(0, M))
+		.take(d)
+		.map(Gfe::new)
+		.collect::<Vec<_>>();
+	coeffs.push(Gfe::one());
+	Polynomial::new(coeffs)
+}
+
 #[test]
 fn random_trials() {
-    const M: u32 =  0x7fffffff; // 2^31-1
-	const TRIALS: usize = 20;
 	const N_MIN: usize = 10;
 	const N_MAX: usize = 50;
 	const K_MIN: usize = 10;
@@ -20,14 +64,13 @@ fn random_trials() {
 		let message = rand::thread_rng()
 			.sample_iter(rand::distributions::Uniform::new(0, M))
 			.take(n)
-			.map(|x| Gfe::<M>::new(x))
+			.map(Gfe::<M>::new)
 			.collect::<Vec<_>>();
 		// Encoded error resistant message
 		let encoded = encode(k, &message);
 
 		// Corrupt the message with e general errors
-		//let e: usize = rand::thread_rng().gen_range(0..=k); // TODO
-		let e = k;
+		let e: usize = rand::thread_rng().gen_range(0..=k);
 		let mut corrupted = encoded.clone();
 		corrupt(&mut corrupted, e);
 
@@ -36,34 +79,436 @@ fn random_trials() {
 	}
 }
 
-fn corrupt<const M: u32>(message: &mut [Gfe<M>], e: usize) {
-	for i in rand::seq::index::sample(&mut rand::thread_rng(), message.len(), e) {
-		message[i] = rand_gfe_except(message[i]);
+#[test]
+fn specific_trials() {
+	let message = gfe_vec(&[1, 5, 3, 4]);
+	let k = 2;
+	let encoded = encode(k, &message);
+	let mut corrupted = encoded.clone();
+	corrupted[1] = Gfe::<19>::new(6);
+	let mut decoded = corrupted.clone();
+	decode(k, &mut decoded).unwrap();
+	println!("Message: {message:?}\nEncoded: {encoded:?}\nCorrupted: {corrupted:?}\nDecoded: {decoded:?}");
+	assert_eq!(&message, &decoded[..message.len()]);
+}
+
+#[test]
+fn no_redundancy_trial() {
+	// With k = 0 the error locator is the constant 1 and decoding divides by it
+	let message = gfe_vec::<19>(&[1, 5, 3, 4]);
+	let mut decoded = encode(0, &message);
+	assert_eq!(decoded, message);
+	decode(0, &mut decoded).unwrap();
+	assert_eq!(decoded, message);
+}
+
+/// Encode the message with the column multipliers v and the given encoding,
+/// corrupt e values and check that decoding restores both the encoded message
+/// and the message
+fn check_round_trip<const M: u32>(k: usize, message: &[Gfe<M>], v: &[Gfe<M>], encoding: Encoding, e: usize) {
+	let encoded = encode_with(k, message, v, encoding);
+	if encoding == Encoding::Systematic {
+		assert_eq!(&encoded[..message.len()], message);
 	}
+	let mut corrupted = encoded.clone();
+	corrupt(&mut corrupted, e);
+	assert_eq!(decode_with(k, &mut corrupted, v, encoding), Ok(message.to_vec()));
+	assert_eq!(corrupted, encoded);
 }
 
-fn rand_gfe_except<const M: u32>(x: Gfe<M>) -> Gfe<M> {
-	let y = rand::thread_rng().sample(rand::distributions::Uniform::new(0, M - 1));
-	if y < *x {
-		Gfe::new(y)
-	} else {
-		Gfe::new(y + 1)
+#[test]
+fn round_trip_trials() {
+	for _ in 0..TRIALS {
+		let n: usize = rand::thread_rng().gen_range(1..=30);
+		let k: usize = rand::thread_rng().gen_range(0..=10);
+		let message = rand_nonzero_gfes::<M>(n);
+		let v = rand_nonzero_gfes::<M>(n + 2 * k);
+		for encoding in [Encoding::Systematic, Encoding::Coefficients] {
+			check_round_trip(k, &message, &v, encoding, k);
+		}
+	}
+
+	// The shortest messages and codes, each with as many errors as they correct
+	for (n, k) in [(1, 0), (1, 1), (1, 8), (8, 0)] {
+		let v = rand_nonzero_gfes::<M>(n + 2 * k);
+		for encoding in [Encoding::Systematic, Encoding::Coefficients] {
+			check_round_trip(k, &rand_nonzero_gfes(n), &v, encoding, k);
+		}
 	}
 }
 
-fn gfe_msg<const M: u32>(ints: &[i64]) -> Vec<Gfe<M>> {
-	ints.iter().map(|&x| Gfe::from(x)).collect()
+#[test]
+#[should_panic(expected = "Expected 7 column multipliers, got 6")]
+fn mismatched_multipliers() {
+	let mut encoded = encode(2, &gfe_vec::<19>(&[3, 1, 4]));
+	let _ = decode_grs(2, &mut encoded, &gfe_vec(&[1, 1, 1, 1, 1, 1]));
 }
 
 #[test]
-fn specific_trials() {
-	let message = gfe_msg(&[1, 5, 3, 4]);
-	let k = 2;
+fn grs_dual_code() {
+	// A code with message length n has length z = n + 2k, its dual has message length 2k and redundancy n.
+	let (n, k) = (6, 3);
+	let z = n + 2 * k;
+	let v = rand_nonzero_gfes::<M>(z);
+	let w = dual_multipliers(&v);
+
+	let a = encode_grs(k, &rand_nonzero_gfes(n), &v);
+	let b = encode_grs(n / 2, &rand_nonzero_gfes(2 * k), &w);
+	let dot = a.iter().zip(b.iter()).fold(Gfe::zero(), |acc, (&x, &y)| acc + x * y);
+	assert_eq!(dot, Gfe::zero());
+}
+
+#[test]
+fn coefficient_encoding_specific() {
+	// f(x) = 1 + 2x evaluated at 0..4
+	let message = gfe_vec::<19>(&[1, 2]);
+	let v = gfe_vec(&[1, 1, 1, 1]);
+	let encoded = encode_with(1, &message, &v, Encoding::Coefficients);
+	assert_eq!(encoded, gfe_vec(&[1, 3, 5, 7]));
+
+	// A trailing zero coefficient is still part of the message
+	let message = gfe_vec::<19>(&[4, 0]);
+	let mut encoded = encode_with(1, &message, &v, Encoding::Coefficients);
+	encoded[2] = Gfe::new(11);
+	assert_eq!(decode_with(1, &mut encoded, &v, Encoding::Coefficients).unwrap(), message);
+}
+
+#[test]
+fn list_decode_beyond_unique_radius() {
+	// Each trial solves a large interpolation system, so run fewer
+	const TRIALS: usize = 5;
+
+	// Length 16 code with message length 4 corrects 6 errors uniquely. The
+	// Guruswami-Sudan bound is 9 errors, but that needs multiplicity 28, so the
+	// list decoder stops at 8.
+	let (n, k) = (4, 6);
+	assert_eq!(list_decoding::max_radius(n + 2 * k, n - 1), 8);
+	for e in [0, 6, 8] {
+		for _ in 0..TRIALS {
+			let message = rand_nonzero_gfes::<M>(n);
+			let encoded = encode(k, &message);
+			let mut corrupted = encoded.clone();
+			corrupt(&mut corrupted, e);
+
+			let candidates = list_decode(k, &corrupted, e).unwrap();
+			assert!(candidates.contains(&message));
+			for candidate in candidates {
+				let distance = encode(k, &candidate).iter().zip(&corrupted).filter(|(a, b)| a != b).count();
+				assert!(distance <= e);
+			}
+		}
+	}
+	assert_eq!(
+		list_decode(k, &encode(k, &rand_nonzero_gfes::<M>(n)), 9),
+		Err(list_decoding::ListDecodeError::RadiusTooLarge { max_radius: 8 })
+	);
+	// Too short for k, as decode reports
+	let mut short = rand_nonzero_gfes::<M>(2 * k);
+	assert!(decode(k, &mut short).is_err());
+	assert_eq!(list_decode(k, &short, 1), Err(list_decoding::ListDecodeError::TooShort));
+}
+
+#[test]
+fn list_decode_multiple_candidates() {
+	// Two messages whose encodings differ in 7 places, and a word between them
+	let (n, k) = (2, 3);
+	let a = encode(k, &gfe_vec::<19>(&[1, 2]));
+	let b = encode(k, &gfe_vec::<19>(&[1, 5]));
+	let agree = a.iter().zip(&b).filter(|(x, y)| x == y).count();
+	assert_eq!(agree, 1);
+	let mut received = a.clone();
+	received[4..].copy_from_slice(&b[4..]);
+
+	let candidates = list_decode(k, &received, 4).unwrap();
+	assert!(candidates.contains(&gfe_vec(&[1, 2])));
+	assert!(candidates.contains(&gfe_vec(&[1, 5])));
+	for candidate in &candidates {
+		let distance = encode(k, candidate).iter().zip(&received).filter(|(x, y)| x != y).count();
+		assert!(distance <= 4);
+	}
+	// Unique decoding only finds b, which is 3 errors away, while a is 4 away
+	assert_eq!(candidates, vec![gfe_vec(&[1, 5]), gfe_vec(&[1, 2])]);
+	let mut decoded = received.clone();
+	decode(k, &mut decoded).unwrap();
+	assert_eq!(decoded, b);
+
+	// Coefficient encoding with multipliers
+	let v = gfe_vec::<19>(&[3, 1, 4, 1, 5, 9, 2, 6]);
+	let message = gfe_vec::<19>(&[7, 11]);
+	let encoded = encode_with(k, &message, &v, Encoding::Coefficients);
+	let mut received = encoded.clone();
+	received[0] = Gfe::new(0);
+	received[3] = Gfe::new(0);
+	received[5] = Gfe::new(0);
+	received[6] = Gfe::new(0);
+	let candidates = list_decode_with(k, &received, &v, Encoding::Coefficients, 4).unwrap();
+	assert!(candidates.contains(&message));
+	for candidate in &candidates {
+		assert_eq!(candidate.len(), n);
+		let codeword = encode_with(k, candidate, &v, Encoding::Coefficients);
+		assert!(codeword.iter().zip(&received).filter(|(x, y)| x != y).count() <= 4);
+	}
+}
+
+#[test]
+fn erasure_trials() {
+	// Every split of the redundancy between erasures and errors with 2e + f <= 2k
+	let (n, k) = (5, 3);
+	let message = rand_nonzero_gfes::<M>(n);
+	let encoded = encode(k, &message);
+	for f in 0..=2 * k {
+		let e = (2 * k - f) / 2;
+		let positions = rand::seq::index::sample(&mut rand::thread_rng(), encoded.len(), e + f).into_vec();
+		let (erasures, errors) = positions.split_at(f);
+		let mut corrupted = encoded.clone();
+		for &i in erasures {
+			corrupted[i] = Gfe::zero();
+		}
+		for &i in errors {
+			corrupted[i] = rand_gfe_except(corrupted[i]);
+		}
+
+		decode_erasures(k, &mut corrupted, erasures).unwrap();
+		assert_eq!(corrupted, encoded);
+	}
+}
+
+#[test]
+fn soft_decoding_beyond_k_errors() {
+	let (n, k) = (8, 4);
+	let message = rand_nonzero_gfes::<M>(n);
 	let encoded = encode(k, &message);
+
+	// Seven errors, more than k, all in positions flagged as unreliable, and one
+	// unreliable position that is actually correct
+	let positions = [0, 2, 5, 7, 9, 11, 14, 15];
 	let mut corrupted = encoded.clone();
-	corrupted[1] = Gfe::<19>::new(6);
-	let mut decoded = corrupted.clone();
-	decode(k, &mut decoded).unwrap();
-	println!("Message: {message:?}\nEncoded: {encoded:?}\nCorrupted: {corrupted:?}\nDecoded: {decoded:?}");
-	assert_eq!(&message, &decoded[..message.len()]);
+	let mut reliability = vec![1.0; encoded.len()];
+	for (j, &i) in positions.iter().enumerate() {
+		reliability[i] = 0.1 + j as f64 * 0.01;
+		if j > 0 {
+			corrupted[i] = rand_gfe_except(corrupted[i]);
+		}
+	}
+
+	decode_soft(k, &mut corrupted, &reliability).unwrap();
+	assert_eq!(corrupted, encoded);
+
+	// Hard errors in reliable positions are corrected like plain decoding
+	let message = gfe_vec::<19>(&[4, 8, 15]);
+	let encoded = encode(2, &message);
+	let mut corrupted = encoded.clone();
+	corrupted[1] = Gfe::new(0);
+	corrupted[5] = Gfe::new(0);
+	let v = vec![Gfe::one(); encoded.len()];
+	let decoded = decode_soft_with(2, &mut corrupted, &v, Encoding::Systematic, &[1.0; 7]).unwrap();
+	assert_eq!(decoded, message);
+	assert_eq!(corrupted, encoded);
+}
+
+#[test]
+fn chase_decoding_beyond_k_errors() {
+	let (n, k) = (6, 3);
+	let message = rand_nonzero_gfes::<M>(n);
+	let encoded = encode(k, &message);
+
+	// Five errors, more than k, where the correct value is the second alternative in
+	// two of the corrupted positions
+	let positions = rand::seq::index::sample(&mut rand::thread_rng(), encoded.len(), 5).into_vec();
+	let mut alternatives = encoded.iter().map(|&x| vec![x]).collect::<Vec<_>>();
+	for (j, &i) in positions.iter().enumerate() {
+		let wrong = rand_gfe_except(encoded[i]);
+		alternatives[i] = if j < 2 { vec![wrong, encoded[i]] } else { vec![wrong] };
+	}
+
+	let result = decode_chase(k, &alternatives, 100).unwrap();
+	assert_eq!(result.trials, 4);
+	let best = &result.candidates[0];
+	assert_eq!(best.codeword, encoded);
+	assert_eq!(best.distance, 2 + 3);
+	assert!(best.trial > 1);
+
+	// Limiting the trials to the hard decision alone fails
+	assert!(decode_chase(k, &alternatives, 1).is_err());
+
+	// A hard decision with correctable errors is found by the first trial
+	let mut alternatives = encoded.iter().map(|&x| vec![x, Gfe::zero()]).collect::<Vec<_>>();
+	alternatives[0][0] = rand_gfe_except(encoded[0]);
+	let result = decode_chase(k, &alternatives, 3).unwrap();
+	assert_eq!(result.trials, 3);
+	assert_eq!(result.candidates[0].codeword, encoded);
+	assert_eq!(result.candidates[0].trial, 1);
+}
+
+#[test]
+fn detection_trials() {
+	let (n, k) = (5, 3);
+	let message = rand_nonzero_gfes::<M>(n);
+	let v = rand_nonzero_gfes::<M>(n + 2 * k);
+	let encoded = encode_grs(k, &message, &v);
+	assert!(is_codeword_grs(k, &encoded, &v));
+	assert!(syndromes_grs(k, &encoded, &v).unwrap().iter().all(|&s| s == Gfe::zero()));
+	assert_eq!(detect_grs(k, &encoded, &v), Ok(()));
+
+	// Every corruption of up to 2k values is detected, even beyond what decode
+	// can correct
+	for e in 1..=2 * k {
+		let mut corrupted = encoded.clone();
+		corrupt(&mut corrupted, e);
+		assert!(!is_codeword_grs(k, &corrupted, &v));
+		assert_eq!(detect_grs(k, &corrupted, &v), Err(()));
+	}
+
+	let encoded = encode(2, &gfe_vec::<19>(&[3, 1, 4]));
+	assert_eq!(syndromes(2, &encoded).unwrap().len(), 4);
+	assert_eq!(detect(2, &encoded), Ok(()));
+
+	// Too short for k, or with the wrong number of multipliers
+	assert_eq!(syndromes(2, &encoded[..4]), Err(()));
+	assert!(!is_codeword(2, &encoded[..4]));
+	assert_eq!(detect(2, &encoded[..4]), Err(()));
+	assert!(!is_codeword_grs(2, &encoded, &gfe_vec(&[1, 1, 1])));
+	let mut corrupted = encoded.clone();
+	corrupted[0] = corrupted[0] + Gfe::one();
+	corrupted[6] = corrupted[6] + Gfe::one();
+	corrupted[3] = corrupted[3] + Gfe::one();
+	assert_eq!(detect(2, &corrupted), Err(()));
+}
+
+#[test]
+fn decode_validation_trials() {
+	const TRIALS: usize = 200;
+
+	// A small field makes miscorrections beyond k errors common enough to test
+	for _ in 0..TRIALS {
+		let n: usize = rand::thread_rng().gen_range(1..=5);
+		let k: usize = rand::thread_rng().gen_range(1..=4);
+		let message = rand_nonzero_gfes::<31>(n);
+		let encoded = encode(k, &message);
+
+		// At most k errors are always corrected
+		for e in [rand::thread_rng().gen_range(0..k), k] {
+			let mut corrupted = encoded.clone();
+			corrupt(&mut corrupted, e);
+			decode(k, &mut corrupted).unwrap();
+			assert_eq!(corrupted, encoded);
+		}
+
+		// More than k errors either fail, or produce a valid codeword within k of
+		// what was received, which is indistinguishable from a correct decoding
+		let e = rand::thread_rng().gen_range((k + 1)..=(n + 2 * k));
+		let mut corrupted = encoded.clone();
+		corrupt(&mut corrupted, e);
+		let received = corrupted.clone();
+		match decode(k, &mut corrupted) {
+			Ok(()) => {
+				assert!(is_codeword(k, &corrupted));
+				assert!(corrupted.iter().zip(&received).filter(|(a, b)| a != b).count() <= k);
+				assert_ne!(corrupted, encoded);
+			}
+			Err(()) => assert_eq!(corrupted, received),
+		}
+	}
+}
+
+#[test]
+fn error_locator_validation() {
+	let known = (0..8).collect::<Vec<_>>();
+	let locator = |roots: &[i64]| Polynomial::<31>::from_roots(&roots.iter().map(|&x| Gfe::from(x)).collect::<Vec<_>>());
+
+	// E = (x - 2)(x - 5) with errors at 2 and 5 is what a correct decoding gives
+	assert!(locator_matches(&locator(&[2, 5]), &known, &[2, 5], true));
+	// A repeated root or a root outside the domain leaves deg E errors unaccounted
+	// for, even though every error found is a root
+	assert!(!locator_matches(&locator(&[2, 2]), &known, &[2], true));
+	assert!(!locator_matches(&locator(&[2, 20]), &known, &[2], true));
+	// An error that E doesn't vanish at
+	assert!(!locator_matches(&locator(&[2, 5]), &known, &[2, 6], true));
+	// With fewer errors than k, E has an arbitrary extra factor
+	assert!(locator_matches(&locator(&[2, 20]), &known, &[2], false));
+	assert!(locator_matches(&locator(&[2, 6]), &known, &[2], false));
+	assert!(!locator_matches(&locator(&[2, 20]), &known, &[3], false));
+}
+
+#[test]
+fn batch_trials() {
+	let k = 5;
+	let messages = (1..=8).map(|n| rand_nonzero_gfes::<M>(n * 3)).collect::<Vec<_>>();
+	let encoded = encode_batch(k, &messages);
+	for (message, encoded) in messages.iter().zip(&encoded) {
+		assert_eq!(encoded, &encode(k, message));
+	}
+
+	let mut corrupted = encoded.clone();
+	for (i, r) in corrupted.iter_mut().enumerate() {
+		corrupt(r, if i == 3 { 2 * k + 1 } else { k });
+	}
+	let mut expected = corrupted.clone();
+	let expected_results = expected.iter_mut().map(|r| decode(k, r)).collect::<Vec<_>>();
+	assert_eq!(decode_batch(k, &mut corrupted), expected_results);
+	assert_eq!(corrupted, expected);
+	for (i, (r, e)) in corrupted.iter().zip(&encoded).enumerate() {
+		if i != 3 {
+			assert_eq!(r, e);
+		}
+	}
+}
+
+#[test]
+fn generator_and_parity_check_matrices() {
+	for (n, k) in [(1, 1), (4, 2), (10, 5)] {
+		let v = rand_nonzero_gfes::<M>(n + 2 * k);
+		let h = parity_check_matrix_grs(n, k, &v);
+		assert_eq!((h.m(), h.n()), (2 * k, n + 2 * k));
+		for encoding in [Encoding::Systematic, Encoding::Coefficients] {
+			let g = generator_matrix_with(n, k, &v, encoding);
+			assert_eq!(&g * &h.transpose(), Matrix::zeros(n, 2 * k));
+
+			// r G is the encoding of r
+			let message = rand_nonzero_gfes::<M>(n);
+			let encoded = encode_with(k, &message, &v, encoding);
+			assert_eq!(&g.transpose() * &message[..], encoded);
+			assert_eq!(h.rank(), 2 * k);
+
+			let mut corrupted = encoded.clone();
+			corrupt(&mut corrupted, 1);
+			assert_eq!(&h * &corrupted[..], syndromes_grs(k, &corrupted, &v).unwrap());
+		}
+	}
+
+	let g = generator_matrix::<19>(3, 2);
+	assert_eq!(g.submatrix(0..3, 0..3), Matrix::identity(3));
+	assert_eq!(&g * &parity_check_matrix(3, 2).transpose(), Matrix::zeros(3, 4));
+}
+
+#[test]
+fn encoder_strategies() {
+	// Both strategies agree with encode_with on fixed messages and multipliers
+	let v = gfe_vec::<19>(&[3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5]);
+	for (n, k) in [(1, 0), (3, 1), (3, 4), (7, 2)] {
+		let v = &v[..n + 2 * k];
+		let messages = [gfe_vec(&vec![0; n]), gfe_vec(&(1..=n as i64).collect::<Vec<_>>())];
+		for encoding in [Encoding::Systematic, Encoding::Coefficients] {
+			let matrix = Encoder::with_strategy(n, k, v, encoding, EncodeStrategy::GeneratorMatrix);
+			let interpolation = Encoder::with_strategy(n, k, v, encoding, EncodeStrategy::Interpolation);
+			assert_eq!(matrix.strategy(), EncodeStrategy::GeneratorMatrix);
+			assert_eq!(interpolation.strategy(), EncodeStrategy::Interpolation);
+			let encoded = messages.iter().map(|r| encode_with(k, r, v, encoding)).collect::<Vec<_>>();
+			assert_eq!(matrix.encode_batch(&messages), encoded);
+			assert_eq!(interpolation.encode_batch(&messages), encoded);
+		}
+	}
+
+	// Codes up to MAX_GENERATOR_LEN long use the generator matrix and longer ones
+	// interpolate
+	let ones = |len| vec![Gfe::<M>::one(); len];
+	let max = Encoder::<M>::MAX_GENERATOR_LEN;
+	assert_eq!(
+		Encoder::new(max - 20, 10, &ones(max), Encoding::Systematic).strategy(),
+		EncodeStrategy::GeneratorMatrix
+	);
+	assert_eq!(
+		Encoder::new(max - 19, 10, &ones(max + 1), Encoding::Systematic).strategy(),
+		EncodeStrategy::Interpolation
+	);
 }